@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use webgl_matrix::{AffineMatrix, Mat4, Matrix};
+
+fn model() -> Mat4 {
+    let mut m = Mat4::identity();
+    m.translate(&[3., -5., 7.]).rotate(0.8, &[1., 2., -1.]);
+    m
+}
+
+fn inverse(c: &mut Criterion) {
+    let m = model();
+
+    let mut group = c.benchmark_group("mat4_inverse");
+    group.bench_function("general", |b| {
+        b.iter(|| {
+            let mut a = black_box(m);
+            a.inverse();
+            a
+        })
+    });
+    group.bench_function("affine", |b| {
+        b.iter(|| {
+            let mut a = black_box(m);
+            a.inverse_affine();
+            a
+        })
+    });
+    group.bench_function("rigid", |b| {
+        b.iter(|| {
+            let mut a = black_box(m);
+            a.inverse_rigid();
+            a
+        })
+    });
+    group.finish();
+}
+
+fn mul(c: &mut Criterion) {
+    let m = model();
+    let mut view = Mat4::identity();
+    view.rotate(-0.3, &[0., 1., 0.]).translate(&[0., -2., 10.]);
+
+    let mut group = c.benchmark_group("mat4_mul");
+    group.bench_function("general", |b| {
+        b.iter(|| {
+            let mut a = black_box(view);
+            a.mul(black_box(&m));
+            a
+        })
+    });
+    group.bench_function("affine", |b| {
+        b.iter(|| {
+            let mut a = black_box(view);
+            a.mul_affine(black_box(&m));
+            a
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, inverse, mul);
+criterion_main!(benches);
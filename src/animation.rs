@@ -0,0 +1,358 @@
+//! Sampling of keyframe animations in the style of glTF.
+//!
+//! A `Track` holds the keyframes of one animated property, e.g. the translation of a node.
+//! Translation and scale tracks hold `Vec3`s, rotation tracks hold `Quat`s:
+//!
+//! ```rust
+//! use webgl_matrix::animation::{Interpolation, Track};
+//!
+//! let track = Track::new(
+//!     Interpolation::Linear,
+//!     vec![0., 1., 2.],
+//!     vec![[0., 0., 0.], [1., 0., 0.], [1., 1., 0.]],
+//! )
+//! .unwrap();
+//! assert_eq!(track.sample(0.5), Some([0.5, 0., 0.]));
+//! ```
+
+use crate::error::{check_len, MathError};
+use crate::quat::{Quat, Quaternion};
+use crate::scalar::Scalar;
+use crate::vec3::Vec3;
+
+#[cfg(feature = "Matrix4")]
+use crate::trs::Trs;
+
+/// The interpolation between keyframes, same as the `interpolation` of a glTF sampler
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Keep the value of the previous keyframe (`STEP`)
+    Step,
+    /// Interpolate linearly, spherically for rotations (`LINEAR`)
+    Linear,
+    /// Interpolate with a cubic Hermite spline (`CUBICSPLINE`)
+    ///
+    /// Each keyframe consists of three values: the in-tangent, the value and the out-tangent.
+    CubicSpline,
+}
+
+/// A value which can be animated by a `Track`
+pub trait Keyframe: Copy {
+    type ScalarType: Scalar;
+
+    /// Interpolate between this value (`t = 0`) and `other` (`t = 1`)
+    fn interpolate(&self, other: &Self, t: Self::ScalarType) -> Self;
+
+    /// Compute the sum of the given values scaled by the given weights
+    fn weighted_sum(values: [&Self; 4], weights: [Self::ScalarType; 4]) -> Self;
+}
+
+impl<T: Scalar> Keyframe for Vec3<T> {
+    type ScalarType = T;
+
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        [
+            self[0] + (other[0] - self[0]) * t,
+            self[1] + (other[1] - self[1]) * t,
+            self[2] + (other[2] - self[2]) * t,
+        ]
+    }
+
+    fn weighted_sum(values: [&Self; 4], weights: [T; 4]) -> Self {
+        let mut out = [T::ZERO; 3];
+        for (v, &w) in values.iter().zip(weights.iter()) {
+            for (o, &x) in out.iter_mut().zip(v.iter()) {
+                *o += x * w;
+            }
+        }
+        out
+    }
+}
+
+/// Rotations are interpolated spherically, the cubic spline result is normalized
+impl<T: Scalar> Keyframe for Quat<T> {
+    type ScalarType = T;
+
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.slerp(other, t)
+    }
+
+    fn weighted_sum(values: [&Self; 4], weights: [T; 4]) -> Self {
+        let mut out = [T::ZERO; 4];
+        for (v, &w) in values.iter().zip(weights.iter()) {
+            for (o, &x) in out.iter_mut().zip(v.iter()) {
+                *o += x * w;
+            }
+        }
+        out.normalize()
+    }
+}
+
+/// The keyframes of one animated property
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track<V: Keyframe> {
+    interpolation: Interpolation,
+    times: Vec<V::ScalarType>,
+    values: Vec<V>,
+}
+
+impl<V: Keyframe> Track<V> {
+    /// Create a track from the given keyframes
+    ///
+    /// Fails if the `times` are not ascending or if the number of `values` does not match the
+    /// number of `times`, which is three values per keyframe for `Interpolation::CubicSpline`.
+    pub fn new(
+        interpolation: Interpolation,
+        times: Vec<V::ScalarType>,
+        values: Vec<V>,
+    ) -> Result<Self, MathError> {
+        // comparisons with NaN are false, a single NaN time is not ordered with itself
+        let ascending = times.windows(2).all(|w| w[0] <= w[1])
+            && times.iter().all(|t| t.partial_cmp(t).is_some());
+        if !ascending {
+            return Err(MathError::NotAscending);
+        }
+
+        let per_keyframe = match interpolation {
+            Interpolation::CubicSpline => 3,
+            _ => 1,
+        };
+        check_len(values.len(), times.len() * per_keyframe)?;
+
+        Ok(Track {
+            interpolation,
+            times,
+            values,
+        })
+    }
+
+    /// The interpolation between the keyframes
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// The times of the keyframes
+    pub fn times(&self) -> &[V::ScalarType] {
+        &self.times
+    }
+
+    /// The values of the keyframes
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    // The value of the given keyframe, without the tangents of cubic splines
+    fn value(&self, keyframe: usize) -> &V {
+        match self.interpolation {
+            Interpolation::CubicSpline => &self.values[3 * keyframe + 1],
+            _ => &self.values[keyframe],
+        }
+    }
+
+    /// Sample the track at the given time. Returns `None` if the track has no keyframes.
+    ///
+    /// Times before the first or after the last keyframe are clamped.
+    pub fn sample(&self, time: V::ScalarType) -> Option<V> {
+        let last = self.times.len().checked_sub(1)?;
+
+        // the number of keyframes at or before `time`
+        let next = self.times.partition_point(|&t| t <= time);
+        if next == 0 {
+            return Some(*self.value(0));
+        }
+        if next > last {
+            return Some(*self.value(last));
+        }
+
+        let prev = next - 1;
+        let dt = self.times[next] - self.times[prev];
+        let s = (time - self.times[prev]) / dt;
+
+        let value = match self.interpolation {
+            Interpolation::Step => *self.value(prev),
+            Interpolation::Linear => self.value(prev).interpolate(self.value(next), s),
+            Interpolation::CubicSpline => {
+                let one = <V::ScalarType as Scalar>::ONE;
+                let two = one + one;
+                let three = two + one;
+                let s2 = s * s;
+                let s3 = s2 * s;
+                V::weighted_sum(
+                    [
+                        &self.values[3 * prev + 1],
+                        &self.values[3 * prev + 2],
+                        &self.values[3 * next],
+                        &self.values[3 * next + 1],
+                    ],
+                    [
+                        two * s3 - three * s2 + one,
+                        dt * (s3 - two * s2 + s),
+                        dt * (s3 - s2),
+                        three * s2 - two * s3,
+                    ],
+                )
+            }
+        };
+        Some(value)
+    }
+}
+
+/// The animated translation, rotation and scale of a node
+#[cfg(feature = "Matrix4")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrsAnimation<T: Scalar = f32> {
+    pub translation: Option<Track<Vec3<T>>>,
+    pub rotation: Option<Track<Quat<T>>>,
+    pub scale: Option<Track<Vec3<T>>>,
+}
+
+#[cfg(feature = "Matrix4")]
+impl<T: Scalar> TrsAnimation<T> {
+    /// Sample all tracks at the given time
+    ///
+    /// Properties without a track (or without keyframes) are taken from `rest`.
+    /// Use `Trs::to_mat4` to compose the result into a matrix.
+    pub fn sample(&self, time: T, rest: Trs<T>) -> Trs<T> {
+        let mut trs = rest;
+        if let Some(translation) = self.translation.as_ref().and_then(|t| t.sample(time)) {
+            trs = trs.with_translation(translation);
+        }
+        if let Some(rotation) = self.rotation.as_ref().and_then(|t| t.sample(time)) {
+            trs = trs.with_rotation(rotation);
+        }
+        if let Some(scale) = self.scale.as_ref().and_then(|t| t.sample(time)) {
+            trs = trs.with_scale(scale);
+        }
+        trs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq;
+
+    fn linear() -> Track<Vec3> {
+        Track::new(
+            Interpolation::Linear,
+            vec![0., 1., 3.],
+            vec![[0., 0., 0.], [1., 0., 0.], [1., 4., 0.]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn animation_track_new() {
+        assert_eq!(
+            Track::new(Interpolation::CubicSpline, vec![0., 1.], vec![[0.; 3]; 2]),
+            Err(MathError::DimensionMismatch {
+                expected: 6,
+                found: 2
+            })
+        );
+
+        assert_eq!(
+            Track::new(Interpolation::Linear, vec![1., 0.], vec![[0.; 3]; 2]),
+            Err(MathError::NotAscending)
+        );
+        assert_eq!(
+            Track::new(Interpolation::Step, vec![f32::NAN], vec![[0.; 3]]),
+            Err(MathError::NotAscending)
+        );
+        assert!(Track::new(Interpolation::Step, vec![0., 0., 1.], vec![[0.; 3]; 3]).is_ok());
+
+        let empty: Track<Vec3> = Track::new(Interpolation::Step, vec![], vec![]).unwrap();
+        assert_eq!(empty.sample(1.), None);
+    }
+
+    #[test]
+    fn animation_linear() {
+        let track = linear();
+        assert_eq!(track.sample(-1.), Some([0., 0., 0.]));
+        assert_eq!(track.sample(0.25), Some([0.25, 0., 0.]));
+        assert_eq!(track.sample(1.), Some([1., 0., 0.]));
+        assert_eq!(track.sample(2.), Some([1., 2., 0.]));
+        assert_eq!(track.sample(5.), Some([1., 4., 0.]));
+    }
+
+    #[test]
+    fn animation_step() {
+        let track = Track::new(
+            Interpolation::Step,
+            linear().times().to_vec(),
+            linear().values().to_vec(),
+        )
+        .unwrap();
+        assert_eq!(track.sample(0.9), Some([0., 0., 0.]));
+        assert_eq!(track.sample(1.), Some([1., 0., 0.]));
+        assert_eq!(track.sample(2.9), Some([1., 0., 0.]));
+    }
+
+    #[test]
+    fn animation_cubic_spline() {
+        // zero tangents result in a smoothstep
+        let zero = [0.; 3];
+        let track = Track::new(
+            Interpolation::CubicSpline,
+            vec![0., 2.],
+            vec![zero, [0., 0., 0.], zero, zero, [2., 0., 0.], zero],
+        )
+        .unwrap();
+        assert!(almost_eq(
+            &track.sample(0.5).unwrap(),
+            &[2. * 0.15625, 0., 0.]
+        ));
+        assert!(almost_eq(&track.sample(1.).unwrap(), &[1., 0., 0.]));
+
+        // constant velocity tangents result in a line
+        let v = [1., 0., 0.];
+        let track = Track::new(
+            Interpolation::CubicSpline,
+            vec![0., 2.],
+            vec![v, [0., 0., 0.], v, v, [2., 0., 0.], v],
+        )
+        .unwrap();
+        assert!(almost_eq(&track.sample(0.5).unwrap(), &[0.5, 0., 0.]));
+    }
+
+    #[test]
+    fn animation_rotation() {
+        let axis = [0., 1., 0.];
+        let track = Track::new(
+            Interpolation::Linear,
+            vec![0., 1.],
+            vec![
+                Quat::from_axis_angle(&axis, 0.),
+                Quat::from_axis_angle(&axis, 2.),
+            ],
+        )
+        .unwrap();
+        assert!(almost_eq(
+            &track.sample(0.25).unwrap(),
+            &Quat::from_axis_angle(&axis, 0.5)
+        ));
+
+        // a repeated key holds the rotation
+        let q: Quat = [0.0022075232, 0.0044371216, -0.00066225696, 0.9999876];
+        let hold = Track::new(Interpolation::Linear, vec![0., 1.], vec![q, q]).unwrap();
+        assert!(almost_eq(&hold.sample(0.5).unwrap(), &q));
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn animation_trs() {
+        let animation = TrsAnimation {
+            translation: Some(linear()),
+            rotation: None,
+            scale: Some(Track::new(Interpolation::Step, vec![0.], vec![[2., 2., 2.]]).unwrap()),
+        };
+        let rest = Trs::identity().with_rotation(Quat::from_axis_angle(&[0., 0., 1.], 1.));
+
+        let trs = animation.sample(2., rest);
+        assert_eq!(
+            trs,
+            rest.with_translation([1., 2., 0.]).with_scale([2., 2., 2.])
+        );
+        assert!(almost_eq(&trs.to_mat4()[12..15], &[1., 2., 0.]));
+    }
+}
@@ -0,0 +1,294 @@
+//! Zero-copy views of vector and matrix slices, e.g. for buffer uploads.
+//!
+//! ```rust
+//! use webgl_matrix::{buffer, Mat4, Matrix};
+//!
+//! let instances: Vec<Mat4> = vec![Mat4::identity(); 8];
+//!
+//! // e.g. for `gl.bufferData`
+//! let floats: &[f32] = buffer::flatten(&instances);
+//! let bytes: &[u8] = buffer::as_bytes(&instances);
+//! assert_eq!(floats.len(), 8 * 16);
+//! assert_eq!(bytes.len(), 8 * 16 * 4);
+//!
+//! let back: &[Mat4] = buffer::try_unflatten(floats).unwrap();
+//! assert_eq!(back, &instances[..]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::slice;
+
+/// Types which consist of a fixed number of tightly packed elements
+///
+/// # Safety
+///
+/// Implementors must have the same size as `[Self::Element; Self::LEN]`, must not have
+/// a larger alignment than `Self::Element` and must be valid for any element values.
+/// `Self::Element` must not contain any padding bytes.
+pub unsafe trait Packed: Copy {
+    type Element: Copy;
+    const LEN: usize;
+}
+
+/// The reasons why a buffer cannot be reinterpreted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastError {
+    /// The buffer length is not a multiple of the target size
+    LengthMismatch { len: usize, multiple: usize },
+    /// The buffer is not sufficiently aligned for the target type
+    Misaligned { align: usize },
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CastError::LengthMismatch { len, multiple } => {
+                write!(f, "buffer length {} is not a multiple of {}", len, multiple)
+            }
+            CastError::Misaligned { align } => {
+                write!(f, "buffer is not aligned to {} bytes", align)
+            }
+        }
+    }
+}
+
+impl Error for CastError {}
+
+fn check<P: Packed, E>(ptr: *const E, len: usize, multiple: usize) -> Result<(), CastError> {
+    if !len.is_multiple_of(multiple) {
+        return Err(CastError::LengthMismatch { len, multiple });
+    }
+    let align = mem::align_of::<P>();
+    if !(ptr as usize).is_multiple_of(align) {
+        return Err(CastError::Misaligned { align });
+    }
+    Ok(())
+}
+
+/// Views the given items as one contiguous slice of their elements
+pub fn flatten<P: Packed>(items: &[P]) -> &[P::Element] {
+    // safe, because `P` consists of exactly `P::LEN` packed elements
+    unsafe { slice::from_raw_parts(items.as_ptr() as *const P::Element, items.len() * P::LEN) }
+}
+
+/// Views the given items as one contiguous mutable slice of their elements
+pub fn flatten_mut<P: Packed>(items: &mut [P]) -> &mut [P::Element] {
+    // safe, because `P` consists of exactly `P::LEN` packed elements
+    unsafe {
+        slice::from_raw_parts_mut(items.as_mut_ptr() as *mut P::Element, items.len() * P::LEN)
+    }
+}
+
+/// Reinterprets the given elements as a slice of items
+///
+/// Fails if the number of elements is not a multiple of `P::LEN`.
+pub fn try_unflatten<P: Packed>(values: &[P::Element]) -> Result<&[P], CastError> {
+    check::<P, _>(values.as_ptr(), values.len(), P::LEN)?;
+    // safe, because the length and alignment have been checked and `P` is valid for any elements
+    Ok(unsafe { slice::from_raw_parts(values.as_ptr() as *const P, values.len() / P::LEN) })
+}
+
+/// Reinterprets the given elements as a mutable slice of items
+///
+/// Fails if the number of elements is not a multiple of `P::LEN`.
+pub fn try_unflatten_mut<P: Packed>(values: &mut [P::Element]) -> Result<&mut [P], CastError> {
+    check::<P, _>(values.as_ptr(), values.len(), P::LEN)?;
+    // safe, because the length and alignment have been checked and `P` is valid for any elements
+    Ok(unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut P, values.len() / P::LEN) })
+}
+
+/// Views the given items as raw bytes in native byte order
+pub fn as_bytes<P: Packed>(items: &[P]) -> &[u8] {
+    // safe, because `P` does not contain any padding
+    unsafe { slice::from_raw_parts(items.as_ptr() as *const u8, mem::size_of_val(items)) }
+}
+
+/// Reinterprets the given raw bytes (in native byte order) as a slice of items
+///
+/// Fails if the number of bytes is not a multiple of the item size or if the bytes
+/// are not aligned properly.
+pub fn try_from_bytes<P: Packed>(bytes: &[u8]) -> Result<&[P], CastError> {
+    let size = mem::size_of::<P>();
+    check::<P, _>(bytes.as_ptr(), bytes.len(), size)?;
+    // safe, because the length and alignment have been checked and `P` is valid for any bytes
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const P, bytes.len() / size) })
+}
+
+macro_rules! impl_packed {
+    ($elem:ty, $($n:expr),+) => {
+        unsafe impl Packed for $elem {
+            type Element = $elem;
+            const LEN: usize = 1;
+        }
+        $(
+            unsafe impl Packed for [$elem; $n] {
+                type Element = $elem;
+                const LEN: usize = $n;
+            }
+        )+
+    };
+}
+
+impl_packed!(f32, 2, 3, 4, 9, 16);
+impl_packed!(f64, 2, 3, 4, 9, 16);
+
+#[cfg(all(feature = "Operators", feature = "Vector3"))]
+unsafe impl<T: Packed> Packed for crate::V3<T> {
+    type Element = T::Element;
+    const LEN: usize = 3 * T::LEN;
+}
+
+#[cfg(all(feature = "Operators", feature = "Vector4"))]
+unsafe impl<T: Packed> Packed for crate::V4<T> {
+    type Element = T::Element;
+    const LEN: usize = 4 * T::LEN;
+}
+
+#[cfg(all(feature = "Operators", feature = "Matrix3"))]
+unsafe impl<T: Packed> Packed for crate::M3<T> {
+    type Element = T::Element;
+    const LEN: usize = 9 * T::LEN;
+}
+
+#[cfg(all(feature = "Operators", feature = "Matrix4"))]
+unsafe impl<T: Packed> Packed for crate::M4<T> {
+    type Element = T::Element;
+    const LEN: usize = 16 * T::LEN;
+}
+
+#[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+unsafe impl<T: Packed> Packed for crate::Trs<T> {
+    type Element = T::Element;
+    const LEN: usize = 10 * T::LEN;
+}
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls {
+    #[cfg(any(
+        all(feature = "Operators", any(feature = "Vector3", feature = "Vector4")),
+        all(feature = "Matrix4", feature = "Quaternion")
+    ))]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    unsafe impl<T: Zeroable> Zeroable for crate::V3<T> {}
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    unsafe impl<T: Pod> Pod for crate::V3<T> {}
+
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    unsafe impl<T: Zeroable> Zeroable for crate::V4<T> {}
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    unsafe impl<T: Pod> Pod for crate::V4<T> {}
+
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    unsafe impl<T: Zeroable> Zeroable for crate::M3<T> {}
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    unsafe impl<T: Pod> Pod for crate::M3<T> {}
+
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    unsafe impl<T: Zeroable> Zeroable for crate::M4<T> {}
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    unsafe impl<T: Pod> Pod for crate::M4<T> {}
+
+    #[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+    unsafe impl<T: Zeroable> Zeroable for crate::Trs<T> {}
+    #[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+    unsafe impl<T: Pod> Pod for crate::Trs<T> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_flatten_round_trip() {
+        let mut vectors = [[1f32, 2., 3.], [4., 5., 6.]];
+
+        assert_eq!(flatten(&vectors), &[1., 2., 3., 4., 5., 6.]);
+
+        flatten_mut(&mut vectors)[4] = 0.;
+        assert_eq!(vectors[1], [4., 0., 6.]);
+
+        let values = [1f32, 2., 3., 4., 5., 6., 7., 8.];
+        let vectors: &[[f32; 4]] = try_unflatten(&values).unwrap();
+        assert_eq!(vectors, &[[1., 2., 3., 4.], [5., 6., 7., 8.]]);
+
+        let mut values = values;
+        let vectors: &mut [[f32; 2]] = try_unflatten_mut(&mut values).unwrap();
+        vectors[3][1] = 0.;
+        assert_eq!(values[7], 0.);
+    }
+
+    #[test]
+    fn buffer_unflatten_checks_length() {
+        let values = [1f32; 18];
+
+        assert_eq!(
+            try_unflatten::<[f32; 16]>(&values),
+            Err(CastError::LengthMismatch {
+                len: 18,
+                multiple: 16
+            })
+        );
+        assert_eq!(try_unflatten::<[f32; 9]>(&values).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn buffer_bytes() {
+        let vectors = [[1f32, 2.], [3., 4.]];
+        let bytes = as_bytes(&vectors);
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(&bytes[4..8], &2f32.to_ne_bytes());
+
+        assert_eq!(try_from_bytes::<[f32; 2]>(bytes), Ok(&vectors[..]));
+        assert_eq!(
+            try_from_bytes::<[f32; 2]>(&bytes[..12]),
+            Err(CastError::LengthMismatch {
+                len: 12,
+                multiple: 8
+            })
+        );
+        assert_eq!(
+            try_from_bytes::<f32>(&bytes[1..5]),
+            Err(CastError::Misaligned { align: 4 })
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    fn buffer_wrappers() {
+        use crate::{Mat4, Matrix, M4};
+
+        let matrices = [M4(Mat4::identity()), M4(Mat4::ones())];
+        let values = flatten(&matrices);
+        assert_eq!(values.len(), 32);
+        assert_eq!(values[16..], [1.; 16]);
+
+        let back: &[M4] = try_unflatten(values).unwrap();
+        assert_eq!(back, &matrices[..]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+    fn buffer_trs() {
+        use crate::Trs;
+
+        let transforms = [Trs::identity().with_translation([1f32, 2., 3.])];
+        assert_eq!(
+            flatten(&transforms),
+            &[1., 2., 3., 0., 0., 0., 1., 1., 1., 1.]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "bytemuck", feature = "Operators", feature = "Matrix4"))]
+    fn buffer_bytemuck() {
+        use crate::{Mat4, Matrix, M4};
+
+        let matrices = [M4(Mat4::<f32>::identity()); 2];
+        let values: &[f32] = bytemuck::cast_slice(&matrices);
+        assert_eq!(values, flatten(&matrices));
+    }
+}
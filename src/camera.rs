@@ -0,0 +1,530 @@
+//! Camera controllers which turn abstract input deltas into view and projection matrices.
+//!
+//! The controllers do not depend on any windowing library: feed them e.g. mouse deltas in
+//! radians or normalized device coordinates, then upload the matrices of the `Camera` trait.
+//! All cameras are right-handed with the Y-axis pointing up, i.e. they look along the
+//! negative Z-axis for a yaw and pitch of zero.
+//!
+//! ```rust
+//! use webgl_matrix::camera::{Camera, OrbitCamera, Perspective};
+//!
+//! let mut camera = OrbitCamera::new([0., 0., 0.], 10., Perspective::new(0.8, 16. / 9., 0.1, 100.));
+//!
+//! // e.g. on mouse move and wheel events
+//! camera.rotate(0.1, 0.2);
+//! camera.zoom(0.5);
+//!
+//! let view = camera.view_matrix();
+//! let projection = camera.projection_matrix();
+//! ```
+
+use crate::mat4::{Mat4, ProjectionMatrix, ViewMatrix};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::utils::clamp;
+use crate::vec3::Vec3;
+
+#[cfg(feature = "Quaternion")]
+use crate::quat::{Quat, Quaternion};
+
+/// The default pitch limit, slightly less than 90° to keep the view direction and the up
+/// vector apart
+const MAX_PITCH: f32 = 1.55;
+
+/// The parameters of a perspective projection
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Perspective<T = f32> {
+    /// The vertical field of view in radians
+    pub fov_y: T,
+    /// The width divided by the height of the viewport
+    pub aspect_ratio: T,
+    pub near: T,
+    pub far: T,
+}
+
+impl<T: Scalar> Perspective<T> {
+    /// Create the parameters of a perspective projection
+    pub fn new(fov_y: T, aspect_ratio: T, near: T, far: T) -> Self {
+        Perspective {
+            fov_y,
+            aspect_ratio,
+            near,
+            far,
+        }
+    }
+
+    /// Compose the projection matrix, see `ProjectionMatrix::create_perspective`
+    pub fn to_mat4(&self) -> Mat4<T> {
+        Mat4::create_perspective(self.fov_y, self.aspect_ratio, self.near, self.far)
+    }
+}
+
+/// The matrices of a camera
+pub trait Camera {
+    type ScalarType: Scalar;
+
+    /// The position of the camera
+    fn eye(&self) -> Vec3<Self::ScalarType>;
+
+    /// The view matrix, which transforms world space into view space
+    fn view_matrix(&self) -> Mat4<Self::ScalarType>;
+
+    /// The projection matrix, which transforms view space into clip space
+    fn projection_matrix(&self) -> Mat4<Self::ScalarType>;
+
+    /// The projection matrix times the view matrix
+    fn view_projection_matrix(&self) -> Mat4<Self::ScalarType> {
+        // `a.mul(b)` computes `b * a` in column-major notation
+        let mut m = self.view_matrix();
+        m.mul(&self.projection_matrix());
+        m
+    }
+}
+
+fn add<T: Scalar>(a: &Vec3<T>, b: &Vec3<T>, scale: T) -> Vec3<T> {
+    [
+        a[0] + b[0] * scale,
+        a[1] + b[1] * scale,
+        a[2] + b[2] * scale,
+    ]
+}
+
+// The unit vector pointing along the negative Z-axis rotated by the given angles
+fn direction<T: Scalar>(yaw: T, pitch: T) -> Vec3<T> {
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    let (sin_pitch, cos_pitch) = pitch.sin_cos();
+    [-cos_pitch * sin_yaw, sin_pitch, -cos_pitch * cos_yaw]
+}
+
+// The pitch used for the view, clamped to the default limit so that it stays well defined for
+// pitch limits of ±90°
+fn view_pitch<T: Scalar>(pitch: T) -> T {
+    let max = T::from_f32(MAX_PITCH);
+    clamp(pitch, -max, max)
+}
+
+// The horizontal unit vector pointing to the right of the given yaw
+fn right_vector<T: Scalar>(yaw: T) -> Vec3<T> {
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    [cos_yaw, T::ZERO, -sin_yaw]
+}
+
+fn world_up<T: Scalar>() -> Vec3<T> {
+    [T::ZERO, T::ONE, T::ZERO]
+}
+
+/// A camera which orbits around a target, also known as turntable camera
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitCamera<T = f32> {
+    /// The point the camera looks at
+    pub target: Vec3<T>,
+    /// The rotation around the Y-axis in radians
+    pub yaw: T,
+    /// The elevation in radians, positive values look down onto the target
+    pub pitch: T,
+    /// The distance between the camera and the target
+    pub distance: T,
+    /// The pitch limits, the view is clamped to just below ±90° regardless
+    pub min_pitch: T,
+    pub max_pitch: T,
+    pub min_distance: T,
+    pub max_distance: T,
+    pub projection: Perspective<T>,
+}
+
+impl<T: Scalar> OrbitCamera<T> {
+    /// Create a camera at the given distance in front of the target, i.e. on the positive Z-axis
+    ///
+    /// The pitch is limited to just below ±90°, the distance is not limited.
+    pub fn new(target: Vec3<T>, distance: T, projection: Perspective<T>) -> Self {
+        let max_pitch = T::from_f32(MAX_PITCH);
+        OrbitCamera {
+            target,
+            yaw: T::ZERO,
+            pitch: T::ZERO,
+            distance,
+            min_pitch: -max_pitch,
+            max_pitch,
+            min_distance: T::ZERO,
+            max_distance: T::from_f32(f32::MAX),
+            projection,
+        }
+    }
+
+    /// Rotate around the target by the given angles in radians, the pitch is clamped
+    pub fn rotate(&mut self, yaw: T, pitch: T) {
+        self.yaw += yaw;
+        self.pitch = clamp(self.pitch + pitch, self.min_pitch, self.max_pitch);
+    }
+
+    /// Move the target parallel to the view plane
+    ///
+    /// The deltas are relative to the distance, e.g. the mouse delta divided by the viewport
+    /// height, so that the target follows the cursor regardless of the zoom.
+    pub fn pan(&mut self, dx: T, dy: T) {
+        let right = right_vector(self.yaw);
+        let forward = direction(self.yaw, -self.pitch);
+        // the up vector of the view plane
+        let up = [
+            right[1] * forward[2] - right[2] * forward[1],
+            right[2] * forward[0] - right[0] * forward[2],
+            right[0] * forward[1] - right[1] * forward[0],
+        ];
+
+        self.target = add(&self.target, &right, dx * self.distance);
+        self.target = add(&self.target, &up, dy * self.distance);
+    }
+
+    /// Multiply the distance by the given factor, e.g. `0.9` to move closer
+    ///
+    /// The distance is clamped.
+    pub fn zoom(&mut self, factor: T) {
+        self.distance = clamp(self.distance * factor, self.min_distance, self.max_distance);
+    }
+}
+
+impl<T: Scalar> Camera for OrbitCamera<T> {
+    type ScalarType = T;
+
+    fn eye(&self) -> Vec3<T> {
+        let forward = direction(self.yaw, -view_pitch(self.pitch));
+        add(&self.target, &forward, -self.distance)
+    }
+
+    fn view_matrix(&self) -> Mat4<T> {
+        Mat4::look_at_rh(&self.eye(), &self.target, &world_up())
+    }
+
+    fn projection_matrix(&self) -> Mat4<T> {
+        self.projection.to_mat4()
+    }
+}
+
+/// A first-person camera, which either walks on the horizontal plane or flies along its
+/// view direction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FirstPersonCamera<T = f32> {
+    pub position: Vec3<T>,
+    /// The rotation around the Y-axis in radians, positive values turn left
+    pub yaw: T,
+    /// The elevation in radians, positive values look up
+    pub pitch: T,
+    /// The pitch limits, the view is clamped to just below ±90° regardless
+    pub min_pitch: T,
+    pub max_pitch: T,
+    pub projection: Perspective<T>,
+}
+
+impl<T: Scalar> FirstPersonCamera<T> {
+    /// Create a camera at the given position, looking along the negative Z-axis
+    ///
+    /// The pitch is limited to just below ±90°.
+    pub fn new(position: Vec3<T>, projection: Perspective<T>) -> Self {
+        let max_pitch = T::from_f32(MAX_PITCH);
+        FirstPersonCamera {
+            position,
+            yaw: T::ZERO,
+            pitch: T::ZERO,
+            min_pitch: -max_pitch,
+            max_pitch,
+            projection,
+        }
+    }
+
+    /// Turn the camera by the given angles in radians, the pitch is clamped
+    pub fn look(&mut self, yaw: T, pitch: T) {
+        self.yaw += yaw;
+        self.pitch = clamp(self.pitch + pitch, self.min_pitch, self.max_pitch);
+    }
+
+    /// The unit vector the camera looks along
+    pub fn forward(&self) -> Vec3<T> {
+        direction(self.yaw, self.pitch)
+    }
+
+    /// Move on the horizontal plane, ignoring the pitch, and vertically along the Y-axis
+    pub fn walk(&mut self, forward: T, right: T, up: T) {
+        let f = direction(self.yaw, T::ZERO);
+        self.position = add(&self.position, &f, forward);
+        self.position = add(&self.position, &right_vector(self.yaw), right);
+        self.position[1] += up;
+    }
+
+    /// Move along the view direction and the right vector, and vertically along the Y-axis
+    pub fn fly(&mut self, forward: T, right: T, up: T) {
+        self.position = add(&self.position, &self.forward(), forward);
+        self.position = add(&self.position, &right_vector(self.yaw), right);
+        self.position[1] += up;
+    }
+}
+
+impl<T: Scalar> Camera for FirstPersonCamera<T> {
+    type ScalarType = T;
+
+    fn eye(&self) -> Vec3<T> {
+        self.position
+    }
+
+    fn view_matrix(&self) -> Mat4<T> {
+        let forward = direction(self.yaw, view_pitch(self.pitch));
+        let target = add(&self.position, &forward, T::ONE);
+        Mat4::look_at_rh(&self.position, &target, &world_up())
+    }
+
+    fn projection_matrix(&self) -> Mat4<T> {
+        self.projection.to_mat4()
+    }
+}
+
+/// A camera which is rotated by dragging on a virtual trackball, as described by Shoemake
+///
+/// Unlike the `OrbitCamera`, the rotation is not restricted, i.e. the camera can roll.
+#[cfg(feature = "Quaternion")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArcballCamera<T = f32> {
+    /// The point the camera looks at
+    pub target: Vec3<T>,
+    /// The rotation of the scene around the target, in view space
+    pub rotation: Quat<T>,
+    /// The distance between the camera and the target
+    pub distance: T,
+    pub min_distance: T,
+    pub max_distance: T,
+    pub projection: Perspective<T>,
+}
+
+// Projects the given point in normalized device coordinates onto the unit sphere
+#[cfg(feature = "Quaternion")]
+fn sphere_point<T: Scalar>(p: &[T; 2]) -> Vec3<T> {
+    let r2 = p[0] * p[0] + p[1] * p[1];
+    if r2 <= T::ONE {
+        [p[0], p[1], (T::ONE - r2).sqrt()]
+    } else {
+        // outside of the ball, use the closest point on its silhouette
+        let r = r2.sqrt();
+        [p[0] / r, p[1] / r, T::ZERO]
+    }
+}
+
+#[cfg(feature = "Quaternion")]
+impl<T: Scalar> ArcballCamera<T> {
+    /// Create a camera at the given distance in front of the target, i.e. on the positive Z-axis
+    pub fn new(target: Vec3<T>, distance: T, projection: Perspective<T>) -> Self {
+        ArcballCamera {
+            target,
+            rotation: Quat::identity(),
+            distance,
+            min_distance: T::ZERO,
+            max_distance: T::from_f32(f32::MAX),
+            projection,
+        }
+    }
+
+    /// Rotate the scene by dragging from one point to another, both in normalized device
+    /// coordinates, i.e. in `[-1, 1]` across the viewport
+    ///
+    /// As in Shoemake's original, the rotation angle is twice the angle between both points
+    /// on the ball. This makes the rotation independent of the path of the cursor.
+    pub fn drag(&mut self, from: &[T; 2], to: &[T; 2]) {
+        let a = sphere_point(from);
+        let b = sphere_point(to);
+
+        let drag = [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+            a[0] * b[0] + a[1] * b[1] + a[2] * b[2],
+        ];
+        self.rotation = drag.mul_quat(&self.rotation).normalize();
+    }
+
+    /// Multiply the distance by the given factor, e.g. `0.9` to move closer
+    ///
+    /// The distance is clamped.
+    pub fn zoom(&mut self, factor: T) {
+        self.distance = clamp(self.distance * factor, self.min_distance, self.max_distance);
+    }
+}
+
+#[cfg(feature = "Quaternion")]
+impl<T: Scalar> Camera for ArcballCamera<T> {
+    type ScalarType = T;
+
+    fn eye(&self) -> Vec3<T> {
+        let (o, l) = (T::ZERO, T::ONE);
+        let back = self.rotation.conjugate().rotate_vector(&[o, o, l]);
+        add(&self.target, &back, self.distance)
+    }
+
+    fn view_matrix(&self) -> Mat4<T> {
+        let up = self.rotation.conjugate().rotate_vector(&world_up::<T>());
+        Mat4::look_at_rh(&self.eye(), &self.target, &up)
+    }
+
+    fn projection_matrix(&self) -> Mat4<T> {
+        self.projection.to_mat4()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn perspective() -> Perspective {
+        Perspective::new(1., 1.5, 0.1, 100.)
+    }
+
+    // the given world space point in view space
+    fn to_view<C: Camera<ScalarType = f32>>(camera: &C, p: &Vec3) -> Vec3 {
+        let v = camera
+            .view_matrix()
+            .mul_vector_left(&[p[0], p[1], p[2], 1.]);
+        [v[0], v[1], v[2]]
+    }
+
+    #[test]
+    fn camera_orbit() {
+        let mut camera = OrbitCamera::new([1., 2., 3.], 5., perspective());
+        assert!(almost_eq(&camera.eye(), &[1., 2., 8.]));
+        assert!(almost_eq(&to_view(&camera, &camera.target), &[0., 0., -5.]));
+
+        // a quarter turn moves the camera onto the positive X-axis
+        camera.rotate(FRAC_PI_2, 0.);
+        assert!(almost_eq(&camera.eye(), &[6., 2., 3.]));
+        assert!(almost_eq(&to_view(&camera, &camera.target), &[0., 0., -5.]));
+
+        // positive pitch looks down
+        camera.rotate(0., 0.5);
+        assert!(camera.eye()[1] > 2.);
+        assert!(almost_eq(&to_view(&camera, &camera.target), &[0., 0., -5.]));
+
+        camera.rotate(0., 10.);
+        assert_eq!(camera.pitch, camera.max_pitch);
+        camera.rotate(0., -20.);
+        assert_eq!(camera.pitch, camera.min_pitch);
+
+        camera.min_distance = 1.;
+        camera.zoom(0.5);
+        assert_eq!(camera.distance, 2.5);
+        camera.zoom(0.1);
+        assert_eq!(camera.distance, 1.);
+    }
+
+    #[test]
+    fn camera_orbit_pan() {
+        let mut camera = OrbitCamera::new([0., 0., 0.], 4., perspective());
+        camera.rotate(0.3, 0.4);
+        let before = to_view(&camera, &[0., 0., 0.]);
+
+        // the old target moves opposite to the pan in view space
+        camera.pan(0.5, 0.25);
+        let after = to_view(&camera, &[0., 0., 0.]);
+        assert!(almost_eq(
+            &[
+                after[0] - before[0],
+                after[1] - before[1],
+                after[2] - before[2]
+            ],
+            &[-2., -1., 0.]
+        ));
+    }
+
+    #[test]
+    fn camera_first_person() {
+        let mut camera = FirstPersonCamera::new([0., 1., 0.], perspective());
+        assert!(almost_eq(&camera.forward(), &[0., 0., -1.]));
+        assert!(almost_eq(&to_view(&camera, &[0., 1., -3.]), &[0., 0., -3.]));
+
+        // turning left
+        camera.look(FRAC_PI_2, 0.);
+        assert!(almost_eq(&camera.forward(), &[-1., 0., 0.]));
+        assert!(almost_eq(&to_view(&camera, &[-3., 1., 0.]), &[0., 0., -3.]));
+
+        // walking ignores the pitch, flying does not
+        camera.look(0., 0.5);
+        camera.walk(2., 1., 0.5);
+        assert!(almost_eq(&camera.position, &[-2., 1.5, -1.]));
+
+        let mut flying = camera;
+        flying.fly(1., 0., 0.);
+        assert!(flying.position[1] > camera.position[1]);
+        assert!(almost_eq(
+            &to_view(&flying, &camera.position),
+            &[0., 0., 1.]
+        ));
+
+        camera.look(0., 10.);
+        assert_eq!(camera.pitch, camera.max_pitch);
+    }
+
+    #[test]
+    fn camera_pitch_limits() {
+        // looking straight down or up must not break the view matrix
+        let mut orbit = OrbitCamera::new([1., 2., 3.], 5., perspective());
+        orbit.max_pitch = FRAC_PI_2;
+        orbit.rotate(0.3, 10.);
+        assert_eq!(orbit.pitch, FRAC_PI_2);
+        assert!(orbit.view_matrix().iter().all(|x| x.is_finite()));
+        assert!(almost_eq(&to_view(&orbit, &orbit.target), &[0., 0., -5.]));
+        assert!(orbit.eye()[1] > 6.99);
+
+        let mut camera = FirstPersonCamera::new([0., 1., 0.], perspective());
+        camera.min_pitch = -FRAC_PI_2;
+        camera.look(0.3, -10.);
+        assert_eq!(camera.pitch, -FRAC_PI_2);
+        assert!(camera.view_matrix().iter().all(|x| x.is_finite()));
+        let v = to_view(&camera, &[0., -2., 0.]);
+        assert!(v[2] < -2.99 && v[0].abs() < 1e-6);
+    }
+
+    #[test]
+    fn camera_view_projection() {
+        let camera = FirstPersonCamera::new([1., 2., 3.], perspective());
+        let mut expected = camera.view_matrix();
+        expected.mul(&perspective().to_mat4());
+        assert_eq!(camera.view_projection_matrix(), expected);
+
+        // a point in front of the camera lands inside the clip volume
+        let clip = camera
+            .view_projection_matrix()
+            .mul_vector_left(&[1., 2., -7., 1.]);
+        assert!(almost_eq(
+            &[clip[0] / clip[3], clip[1] / clip[3]],
+            &[0., 0.]
+        ));
+        assert!(clip[2].abs() < clip[3]);
+    }
+
+    #[cfg(feature = "Quaternion")]
+    #[test]
+    fn camera_arcball() {
+        let mut camera = ArcballCamera::new([0., 0., 0.], 5., perspective());
+        assert!(almost_eq(&camera.eye(), &[0., 0., 5.]));
+
+        // dragging from the center to the right edge turns the scene by 180° around the Y-axis
+        camera.drag(&[0., 0.], &[1., 0.]);
+        assert!(almost_eq(&camera.eye(), &[0., 0., -5.]));
+        assert!(almost_eq(&to_view(&camera, &[0., 0., 0.]), &[0., 0., -5.]));
+
+        // a point in front of the target follows the cursor, by twice the angle on the ball
+        let mut camera = ArcballCamera::new([0., 0., 0.], 5., perspective());
+        camera.drag(&[0., 0.], &[0.5, 0.]);
+        let (s, c) = (std::f32::consts::PI / 3.).sin_cos();
+        assert!(almost_eq(
+            &to_view(&camera, &[0., 0., 1.]),
+            &[s, 0., c - 5.]
+        ));
+
+        // the rotation is independent of the path
+        let mut moved = camera;
+        moved.drag(&[0.1, 0.2], &[0.4, -0.3]);
+        moved.drag(&[0.4, -0.3], &[-0.2, 0.1]);
+        moved.drag(&[-0.2, 0.1], &[0.1, 0.2]);
+        assert!(almost_eq(&moved.view_matrix(), &camera.view_matrix()));
+
+        camera.zoom(2.);
+        assert_eq!(camera.distance, 10.);
+    }
+}
@@ -0,0 +1,457 @@
+//! Color space conversions on RGB(A) vectors.
+//!
+//! Colors are `Vec3` (RGB) or `Vec4` (RGBA) with channels in `[0, 1]`. Functions taking
+//! `[T; N]` accept both, the alpha channel is passed through unchanged:
+//!
+//! ```rust
+//! use webgl_matrix::color;
+//! use webgl_matrix::Vec4;
+//!
+//! let srgb: Vec4 = color::parse_hex("#ff800080").unwrap();
+//! let linear = color::srgb_to_linear(&srgb);
+//! let uniform = color::premultiply(&linear);
+//! assert!((uniform[3] - 0.5).abs() < 1e-2);
+//! ```
+//!
+//! Hues (HSV, HSL, Oklch) are angles in radians in `[0, 2π)`, so they can be interpolated
+//! with `utils::lerp_angle`. Oklab and Oklch are computed from linear RGB.
+
+use crate::scalar::Scalar;
+use crate::utils::{modulo, pi, wrap_angle_positive};
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "Matrix3")]
+use crate::{mat3::Mat3, matrix::RotationMatrix};
+
+/// Decode a single sRGB channel to linear using the exact piecewise transfer function
+pub fn srgb_decode<T: Scalar>(c: T) -> T {
+    if c <= T::from_f32(0.04045) {
+        c / T::from_f32(12.92)
+    } else {
+        ((c + T::from_f32(0.055)) / T::from_f32(1.055)).powf(T::from_f32(2.4))
+    }
+}
+
+/// Encode a single linear channel to sRGB using the exact piecewise transfer function
+pub fn srgb_encode<T: Scalar>(c: T) -> T {
+    if c <= T::from_f32(0.0031308) {
+        c * T::from_f32(12.92)
+    } else {
+        T::from_f32(1.055) * c.powf(T::ONE / T::from_f32(2.4)) - T::from_f32(0.055)
+    }
+}
+
+/// Convert the RGB channels of the given sRGB color to linear RGB
+pub fn srgb_to_linear<T: Scalar, const N: usize>(color: &[T; N]) -> [T; N] {
+    map_rgb(color, srgb_decode)
+}
+
+/// Convert the RGB channels of the given linear color to sRGB
+pub fn linear_to_srgb<T: Scalar, const N: usize>(color: &[T; N]) -> [T; N] {
+    map_rgb(color, srgb_encode)
+}
+
+fn map_rgb<T: Scalar, const N: usize>(color: &[T; N], f: fn(T) -> T) -> [T; N] {
+    let mut out = *color;
+    for c in out.iter_mut().take(3) {
+        *c = f(*c);
+    }
+    out
+}
+
+/// Multiply the RGB channels of the given color by its alpha
+pub fn premultiply<T: Scalar>(color: &[T; 4]) -> [T; 4] {
+    let a = color[3];
+    [color[0] * a, color[1] * a, color[2] * a, a]
+}
+
+/// Divide the RGB channels of the given premultiplied color by its alpha
+///
+/// A fully transparent color results in transparent black.
+pub fn unpremultiply<T: Scalar>(color: &[T; 4]) -> [T; 4] {
+    let a = color[3];
+    if a == T::ZERO {
+        return [T::ZERO; 4];
+    }
+    [color[0] / a, color[1] / a, color[2] / a, a]
+}
+
+fn max3<T: Scalar>(a: T, b: T, c: T) -> T {
+    let ab = if a > b { a } else { b };
+    if ab > c {
+        ab
+    } else {
+        c
+    }
+}
+
+fn min3<T: Scalar>(a: T, b: T, c: T) -> T {
+    let ab = if a < b { a } else { b };
+    if ab < c {
+        ab
+    } else {
+        c
+    }
+}
+
+// The hue shared by HSV and HSL, `delta` is the difference of the largest and smallest channel
+fn hue<T: Scalar>(rgb: &[T; 3], max: T, delta: T) -> T {
+    if delta == T::ZERO {
+        return T::ZERO;
+    }
+    let [r, g, b] = *rgb;
+    let sector = if max == r {
+        modulo((g - b) / delta, T::from_f32(6.))
+    } else if max == g {
+        (b - r) / delta + T::from_f32(2.)
+    } else {
+        (r - g) / delta + T::from_f32(4.)
+    };
+    sector * pi::<T>() / T::from_f32(3.)
+}
+
+/// Convert the given RGB color to HSV (hue, saturation, value)
+pub fn rgb_to_hsv<T: Scalar>(rgb: &[T; 3]) -> [T; 3] {
+    let max = max3(rgb[0], rgb[1], rgb[2]);
+    let delta = max - min3(rgb[0], rgb[1], rgb[2]);
+    let s = if max > T::ZERO { delta / max } else { T::ZERO };
+    [hue(rgb, max, delta), s, max]
+}
+
+/// Convert the given HSV (hue, saturation, value) color to RGB
+pub fn hsv_to_rgb<T: Scalar>(hsv: &[T; 3]) -> [T; 3] {
+    let [h, s, v] = *hsv;
+    let sector = h / pi::<T>() * T::from_f32(3.);
+    let channel = |n: f32| {
+        let k = modulo(T::from_f32(n) + sector, T::from_f32(6.));
+        let ramp = min3(k, T::from_f32(4.) - k, T::ONE);
+        let ramp = if ramp > T::ZERO { ramp } else { T::ZERO };
+        v - v * s * ramp
+    };
+    [channel(5.), channel(3.), channel(1.)]
+}
+
+/// Convert the given RGB color to HSL (hue, saturation, lightness)
+pub fn rgb_to_hsl<T: Scalar>(rgb: &[T; 3]) -> [T; 3] {
+    let max = max3(rgb[0], rgb[1], rgb[2]);
+    let min = min3(rgb[0], rgb[1], rgb[2]);
+    let delta = max - min;
+    let l = (max + min) / T::from_f32(2.);
+    let s = if delta == T::ZERO {
+        T::ZERO
+    } else {
+        delta / (T::ONE - (l + l - T::ONE).abs())
+    };
+    [hue(rgb, max, delta), s, l]
+}
+
+/// Convert the given HSL (hue, saturation, lightness) color to RGB
+pub fn hsl_to_rgb<T: Scalar>(hsl: &[T; 3]) -> [T; 3] {
+    let [h, s, l] = *hsl;
+    let a = s * if l < T::ONE - l { l } else { T::ONE - l };
+    let sector = h / pi::<T>() * T::from_f32(6.);
+    let channel = |n: f32| {
+        let k = modulo(T::from_f32(n) + sector, T::from_f32(12.));
+        let ramp = min3(k - T::from_f32(3.), T::from_f32(9.) - k, T::ONE);
+        let ramp = if ramp > -T::ONE { ramp } else { -T::ONE };
+        l - a * ramp
+    };
+    [channel(0.), channel(8.), channel(4.)]
+}
+
+// Row-major 3x3 matrix times vector, for the constant matrices of Oklab
+fn transform<T: Scalar>(m: &[[f32; 3]; 3], v: &[T; 3]) -> [T; 3] {
+    let row = |r: &[f32; 3]| {
+        T::from_f32(r[0]) * v[0] + T::from_f32(r[1]) * v[1] + T::from_f32(r[2]) * v[2]
+    };
+    [row(&m[0]), row(&m[1]), row(&m[2])]
+}
+
+fn cbrt<T: Scalar>(x: T) -> T {
+    let r = x.abs().powf(T::ONE / T::from_f32(3.));
+    if x < T::ZERO {
+        -r
+    } else {
+        r
+    }
+}
+
+// The matrices of Björn Ottosson's reference implementation, digits kept for comparison
+#[allow(clippy::excessive_precision)]
+const RGB_TO_LMS: [[f32; 3]; 3] = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+#[allow(clippy::excessive_precision)]
+const LMS_TO_OKLAB: [[f32; 3]; 3] = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+#[allow(clippy::excessive_precision)]
+const OKLAB_TO_LMS: [[f32; 3]; 3] = [
+    [1., 0.3963377774, 0.2158037573],
+    [1., -0.1055613458, -0.0638541728],
+    [1., -0.0894841775, -1.2914855480],
+];
+
+#[allow(clippy::excessive_precision)]
+const LMS_TO_RGB: [[f32; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+/// Convert the given linear RGB color to Oklab (lightness, a, b)
+pub fn linear_to_oklab<T: Scalar>(rgb: &[T; 3]) -> [T; 3] {
+    let lms = transform(&RGB_TO_LMS, rgb);
+    transform(&LMS_TO_OKLAB, &[cbrt(lms[0]), cbrt(lms[1]), cbrt(lms[2])])
+}
+
+/// Convert the given Oklab (lightness, a, b) color to linear RGB
+///
+/// Colors outside of the sRGB gamut result in channels outside of `[0, 1]`.
+pub fn oklab_to_linear<T: Scalar>(lab: &[T; 3]) -> [T; 3] {
+    let lms = transform(&OKLAB_TO_LMS, lab);
+    let cube = |x: T| x * x * x;
+    transform(&LMS_TO_RGB, &[cube(lms[0]), cube(lms[1]), cube(lms[2])])
+}
+
+/// Convert the given Oklab (lightness, a, b) color to Oklch (lightness, chroma, hue)
+///
+/// Grays, i.e. colors with a chroma below `1e-6`, have the hue 0.
+pub fn oklab_to_oklch<T: Scalar>(lab: &[T; 3]) -> [T; 3] {
+    let [l, a, b] = *lab;
+    let c = (a * a + b * b).sqrt();
+    let h = if c < T::from_f32(1e-6) {
+        T::ZERO
+    } else {
+        wrap_angle_positive(b.atan2(a))
+    };
+    [l, c, h]
+}
+
+/// Convert the given Oklch (lightness, chroma, hue) color to Oklab (lightness, a, b)
+pub fn oklch_to_oklab<T: Scalar>(lch: &[T; 3]) -> [T; 3] {
+    let [l, c, h] = *lch;
+    let (sin, cos) = h.sin_cos();
+    [l, c * cos, c * sin]
+}
+
+/// Convert the given linear RGB color to Oklch (lightness, chroma, hue)
+pub fn linear_to_oklch<T: Scalar>(rgb: &[T; 3]) -> [T; 3] {
+    oklab_to_oklch(&linear_to_oklab(rgb))
+}
+
+/// Convert the given Oklch (lightness, chroma, hue) color to linear RGB
+pub fn oklch_to_linear<T: Scalar>(lch: &[T; 3]) -> [T; 3] {
+    oklab_to_linear(&oklch_to_oklab(lch))
+}
+
+/// The reasons why a hex color cannot be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The number of hex digits is not 3, 4, 6 or 8
+    InvalidLength(usize),
+    /// A character is not a hex digit
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidLength(len) => {
+                write!(f, "expected 3, 4, 6 or 8 hex digits, found {}", len)
+            }
+            ParseColorError::InvalidDigit(c) => write!(f, "invalid hex digit `{}`", c),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// Parse a CSS hex color (`#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`) into an sRGB color
+///
+/// The leading `#` is optional. Without an alpha channel the color is opaque.
+pub fn parse_hex<T: Scalar>(s: &str) -> Result<[T; 4], ParseColorError> {
+    let s = s.trim();
+    let s = s.strip_prefix('#').unwrap_or(s);
+    let digits = s
+        .chars()
+        .map(|c| c.to_digit(16).ok_or(ParseColorError::InvalidDigit(c)))
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    let mut out = [T::ONE; 4];
+    match digits.len() {
+        3 | 4 => {
+            for (o, d) in out.iter_mut().zip(&digits) {
+                *o = T::from_f32(*d as f32 / 15.);
+            }
+        }
+        6 | 8 => {
+            for (o, d) in out.iter_mut().zip(digits.chunks(2)) {
+                *o = T::from_f32((d[0] * 16 + d[1]) as f32 / 255.);
+            }
+        }
+        len => return Err(ParseColorError::InvalidLength(len)),
+    }
+    Ok(out)
+}
+
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+/// The relative luminance of the given linear RGB color (Rec. 709 weights)
+pub fn luminance<T: Scalar>(rgb: &[T; 3]) -> T {
+    T::from_f32(LUMA[0]) * rgb[0] + T::from_f32(LUMA[1]) * rgb[1] + T::from_f32(LUMA[2]) * rgb[2]
+}
+
+/// Create a color matrix which scales the saturation of linear RGB colors
+///
+/// `0` results in grayscale with the same luminance, `1` in the identity and larger values
+/// oversaturate. Apply it with `mul_vector_left`.
+#[cfg(feature = "Matrix3")]
+pub fn saturation_matrix<T: Scalar>(saturation: T) -> Mat3<T> {
+    let mut out = [T::ZERO; 9];
+    for col in 0..3 {
+        for row in 0..3 {
+            let diagonal = if row == col { saturation } else { T::ZERO };
+            out[col * 3 + row] = (T::ONE - saturation) * T::from_f32(LUMA[col]) + diagonal;
+        }
+    }
+    out
+}
+
+/// Create a color matrix which rotates the hue of RGB colors by the given angle (radians)
+///
+/// This is a rotation around the gray axis, i.e. grays are preserved and a rotation by
+/// `2π / 3` turns red into green. Apply it with `mul_vector_left`.
+#[cfg(feature = "Matrix3")]
+pub fn hue_rotation_matrix<T: Scalar>(angle: T) -> Mat3<T> {
+    Mat3::from_axis_angle(&[T::ONE; 3], angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{almost_eq, almost_eq_relative};
+
+    const COLORS: [[f64; 3]; 6] = [
+        [0., 0., 0.],
+        [1., 1., 1.],
+        [1., 0., 0.],
+        [0.2, 0.6, 0.4],
+        [0.9, 0.1, 0.7],
+        [0.3, 0.3, 0.8],
+    ];
+
+    #[test]
+    fn color_srgb() {
+        assert!((srgb_decode(0.5f64) - 0.2140411).abs() < 1e-6);
+        assert!((srgb_encode(0.2140411f64) - 0.5).abs() < 1e-6);
+        assert_eq!(srgb_decode(0.04f32), 0.04 / 12.92);
+        assert_eq!(srgb_encode(0f32), 0.);
+
+        let c = [0.25f64, 0.5, 0.75, 0.5];
+        let linear = srgb_to_linear(&c);
+        assert_eq!(linear[3], 0.5);
+        assert!(almost_eq(&linear_to_srgb(&linear), &c));
+        assert!(almost_eq(&srgb_to_linear(&[1f32, 0., 1.]), &[1., 0., 1.]));
+    }
+
+    #[test]
+    fn color_premultiply() {
+        let c = [1f32, 0.5, 0.25, 0.5];
+        assert_eq!(premultiply(&c), [0.5, 0.25, 0.125, 0.5]);
+        assert_eq!(unpremultiply(&premultiply(&c)), c);
+        assert_eq!(unpremultiply(&[0.2f32, 0.2, 0.2, 0.]), [0.; 4]);
+    }
+
+    #[test]
+    fn color_hsv_hsl() {
+        let pi = std::f64::consts::PI;
+        assert!(almost_eq(&rgb_to_hsv(&[1f64, 0., 0.]), &[0., 1., 1.]));
+        assert!(almost_eq(&rgb_to_hsv(&[0f64, 1., 1.]), &[pi, 1., 1.]));
+        assert!(almost_eq(
+            &rgb_to_hsv(&[1f64, 0., 0.5]),
+            &[pi * 11. / 6., 1., 1.]
+        ));
+        assert!(almost_eq(&rgb_to_hsl(&[1f64, 0.5, 0.5]), &[0., 1., 0.75]));
+        assert!(almost_eq(
+            &rgb_to_hsl(&[0f64, 0., 0.5]),
+            &[pi * 4. / 3., 1., 0.25]
+        ));
+        assert!(almost_eq(
+            &hsv_to_rgb(&[pi * 2. / 3., 1., 1.]),
+            &[0., 1., 0.]
+        ));
+        assert!(almost_eq(&hsl_to_rgb(&[pi / 3., 1., 0.5]), &[1., 1., 0.]));
+
+        for c in &COLORS {
+            assert!(almost_eq(&hsv_to_rgb(&rgb_to_hsv(c)), c));
+            assert!(almost_eq(&hsl_to_rgb(&rgb_to_hsl(c)), c));
+        }
+    }
+
+    #[test]
+    fn color_oklab() {
+        // the constant matrices are stored in `f32` precision
+        let close = |a: &[f64; 3], b: &[f64; 3]| almost_eq_relative(a, b, 1e-6, 0.);
+
+        assert!(close(&linear_to_oklab(&[1f64, 1., 1.]), &[1., 0., 0.]));
+        let red = linear_to_oklab(&[1f64, 0., 0.]);
+        assert!(close(&red, &[0.6279554, 0.2248631, 0.1258463]));
+        let red = oklab_to_oklch(&red);
+        assert!(close(
+            &red,
+            &[0.6279554, 0.2576833, 29.2338851f64.to_radians()]
+        ));
+
+        // hues are positive
+        assert!(linear_to_oklch(&[0f64, 0., 1.])[2] > 0.);
+        assert_eq!(linear_to_oklch(&[0.5f64, 0.5, 0.5])[2], 0.);
+
+        for c in &COLORS {
+            assert!(close(&oklab_to_linear(&linear_to_oklab(c)), c));
+            assert!(close(&oklch_to_linear(&linear_to_oklch(c)), c));
+        }
+    }
+
+    #[test]
+    fn color_hex() {
+        assert_eq!(parse_hex::<f32>("#ff0000"), Ok([1., 0., 0., 1.]));
+        assert_eq!(parse_hex::<f32>("00FF0080"), Ok([0., 1., 0., 128. / 255.]));
+        assert_eq!(parse_hex::<f32>(" #f0a "), Ok([1., 0., 2. / 3., 1.]));
+        assert_eq!(parse_hex::<f32>("#0000"), Ok([0.; 4]));
+        assert_eq!(
+            parse_hex::<f32>("#12345"),
+            Err(ParseColorError::InvalidLength(5))
+        );
+        assert_eq!(
+            parse_hex::<f32>("#12g"),
+            Err(ParseColorError::InvalidDigit('g'))
+        );
+    }
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn color_grading() {
+        use crate::matrix::Matrix;
+
+        let c = [0.8f64, 0.4, 0.1];
+        let gray = saturation_matrix(0.).mul_vector_left(&c);
+        assert!(almost_eq(&gray, &[luminance(&c); 3]));
+        assert!(almost_eq(&saturation_matrix(1.).mul_vector_left(&c), &c));
+        let vivid = saturation_matrix(2.).mul_vector_left(&c);
+        assert!((luminance(&vivid) - luminance(&c)).abs() < 1e-10);
+        assert!(rgb_to_hsv(&vivid)[1] > rgb_to_hsv(&c)[1]);
+
+        let pi = std::f64::consts::PI;
+        let m = hue_rotation_matrix(pi * 2. / 3.);
+        assert!(almost_eq(&m.mul_vector_left(&[1., 0., 0.]), &[0., 1., 0.]));
+        assert!(almost_eq(&m.mul_vector_left(&[0.5, 0.5, 0.5]), &[0.5; 3]));
+    }
+}
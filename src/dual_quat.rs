@@ -0,0 +1,388 @@
+#[cfg(feature = "Matrix4")]
+use crate::mat4::Mat4;
+use crate::quat::{Quat, Quaternion};
+use crate::scalar::Scalar;
+use crate::vec3::Vec3;
+
+/// A dual quaternion stored as `[real, dual]`, i.e. `[x, y, z, w, dx, dy, dz, dw]`
+///
+/// Unit dual quaternions represent rigid transformations, i.e. a rotation followed by
+/// a translation. The layout is the same as `quat2` of gl-matrix.
+pub type DualQuat<T = f32> = [T; 8];
+
+/// Dual quaternion operations for rigid transformations and skinning
+pub trait DualQuaternion {
+    type DualQuaternionType;
+    type ScalarType: Scalar;
+
+    /// Create the identity transformation
+    fn identity() -> Self::DualQuaternionType;
+
+    /// Create the transformation applying the given rotation first and the translation second
+    fn from_rotation_translation(
+        rotation: &Quat<Self::ScalarType>,
+        translation: &[Self::ScalarType],
+    ) -> Self::DualQuaternionType;
+
+    /// The rotation part, i.e. the real quaternion
+    fn rotation(&self) -> Quat<Self::ScalarType>;
+
+    /// The translation part
+    fn translation(&self) -> Vec3<Self::ScalarType>;
+
+    /// Compute the product `self * rhs`, i.e. the transformation `rhs` followed by `self`
+    fn mul_dual_quat(&self, rhs: &Self::DualQuaternionType) -> Self::DualQuaternionType;
+
+    /// Compute the quaternion conjugate of both parts, which is the inverse transformation
+    /// for unit dual quaternions
+    fn conjugate(&self) -> Self::DualQuaternionType;
+
+    /// Scale this dual quaternion to unit length and make the dual part orthogonal to the real part
+    fn normalize(&self) -> Self::DualQuaternionType;
+
+    /// Transform the given 3-dimensional point
+    fn transform_point(&self, p: &[Self::ScalarType]) -> Vec3<Self::ScalarType>;
+
+    /// Screw linear interpolation between this transformation (`t = 0`) and `other` (`t = 1`)
+    ///
+    /// Both operands have to be normalized. The shortest path is taken.
+    fn sclerp(
+        &self,
+        other: &Self::DualQuaternionType,
+        t: Self::ScalarType,
+    ) -> Self::DualQuaternionType;
+
+    /// Dual quaternion linear blending (DLB) of the given transformations by the given weights
+    ///
+    /// Unlike blending matrices, this does not collapse the volume around twisted joints.
+    /// All transformations are flipped into the hemisphere of the first one.
+    fn blend(
+        transforms: &[Self::DualQuaternionType],
+        weights: &[Self::ScalarType],
+    ) -> Self::DualQuaternionType;
+
+    /// Create the transformation from the given rigid matrix
+    #[cfg(feature = "Matrix4")]
+    fn from_mat4(m: &Mat4<Self::ScalarType>) -> Self::DualQuaternionType;
+
+    /// Convert this transformation to a homogenous matrix
+    #[cfg(feature = "Matrix4")]
+    fn to_mat4(&self) -> Mat4<Self::ScalarType>;
+}
+
+fn real<T: Scalar>(d: &DualQuat<T>) -> Quat<T> {
+    [d[0], d[1], d[2], d[3]]
+}
+
+fn dual<T: Scalar>(d: &DualQuat<T>) -> Quat<T> {
+    [d[4], d[5], d[6], d[7]]
+}
+
+fn join<T: Scalar>(real: &Quat<T>, dual: &Quat<T>) -> DualQuat<T> {
+    [
+        real[0], real[1], real[2], real[3], dual[0], dual[1], dual[2], dual[3],
+    ]
+}
+
+fn dot4<T: Scalar>(a: &Quat<T>, b: &Quat<T>) -> T {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+}
+
+// `d^t` of a unit dual quaternion using its screw parameters
+fn pow<T: Scalar>(d: &DualQuat<T>, t: T) -> DualQuat<T> {
+    let r = real(d);
+    let e = dual(d);
+
+    let sin = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
+    if sin <= T::EPSILON {
+        // pure translation
+        return [
+            T::ZERO,
+            T::ZERO,
+            T::ZERO,
+            T::ONE,
+            e[0] * t,
+            e[1] * t,
+            e[2] * t,
+            T::ZERO,
+        ];
+    }
+
+    let cos = r[3];
+    let angle = sin.atan2(cos);
+    // the screw axis `l`, its moment `m` and the half pitch `h`
+    let l = [r[0] / sin, r[1] / sin, r[2] / sin];
+    let h = -e[3] / sin;
+    let m = [
+        (e[0] - l[0] * h * cos) / sin,
+        (e[1] - l[1] * h * cos) / sin,
+        (e[2] - l[2] * h * cos) / sin,
+    ];
+
+    let (s, c) = (angle * t).sin_cos();
+    let h = h * t;
+    [
+        l[0] * s,
+        l[1] * s,
+        l[2] * s,
+        c,
+        m[0] * s + l[0] * h * c,
+        m[1] * s + l[1] * h * c,
+        m[2] * s + l[2] * h * c,
+        -h * s,
+    ]
+}
+
+impl<T: Scalar> DualQuaternion for DualQuat<T> {
+    type DualQuaternionType = DualQuat<T>;
+    type ScalarType = T;
+
+    fn identity() -> Self {
+        let o = T::ZERO;
+        [o, o, o, T::ONE, o, o, o, o]
+    }
+
+    fn from_rotation_translation(rotation: &Quat<T>, translation: &[T]) -> Self {
+        debug_assert!(translation.len() >= 3);
+
+        let half = T::from_f32(0.5);
+        let t = [
+            translation[0] * half,
+            translation[1] * half,
+            translation[2] * half,
+            T::ZERO,
+        ];
+        join(rotation, &t.mul_quat(rotation))
+    }
+
+    fn rotation(&self) -> Quat<T> {
+        real(self)
+    }
+
+    fn translation(&self) -> Vec3<T> {
+        let two = T::ONE + T::ONE;
+        let t = dual(self).mul_quat(&real(self).conjugate());
+        [t[0] * two, t[1] * two, t[2] * two]
+    }
+
+    fn mul_dual_quat(&self, rhs: &Self) -> Self {
+        let (ar, ad) = (real(self), dual(self));
+        let (br, bd) = (real(rhs), dual(rhs));
+
+        let r = ar.mul_quat(&br);
+        let d1 = ar.mul_quat(&bd);
+        let d2 = ad.mul_quat(&br);
+        join(
+            &r,
+            &[d1[0] + d2[0], d1[1] + d2[1], d1[2] + d2[2], d1[3] + d2[3]],
+        )
+    }
+
+    fn conjugate(&self) -> Self {
+        join(&real(self).conjugate(), &dual(self).conjugate())
+    }
+
+    fn normalize(&self) -> Self {
+        let r = real(self);
+        let len = dot4(&r, &r).sqrt();
+        if len <= T::EPSILON {
+            return Self::identity();
+        }
+
+        let inv = T::ONE / len;
+        let r = [r[0] * inv, r[1] * inv, r[2] * inv, r[3] * inv];
+        let e = dual(self);
+        let e = [e[0] * inv, e[1] * inv, e[2] * inv, e[3] * inv];
+
+        // remove the part of the dual which is parallel to the real part
+        let d = dot4(&r, &e);
+        join(
+            &r,
+            &[
+                e[0] - r[0] * d,
+                e[1] - r[1] * d,
+                e[2] - r[2] * d,
+                e[3] - r[3] * d,
+            ],
+        )
+    }
+
+    fn transform_point(&self, p: &[T]) -> Vec3<T> {
+        let r = real(self).rotate_vector(p);
+        let t = self.translation();
+        [r[0] + t[0], r[1] + t[1], r[2] + t[2]]
+    }
+
+    fn sclerp(&self, other: &Self, t: T) -> Self {
+        let mut other = *other;
+        if dot4(&real(self), &real(&other)) < T::ZERO {
+            for x in &mut other {
+                *x = -*x;
+            }
+        }
+
+        // `self * (self^-1 * other)^t`
+        let delta = self.conjugate().mul_dual_quat(&other);
+        self.mul_dual_quat(&pow(&delta, t)).normalize()
+    }
+
+    fn blend(transforms: &[Self], weights: &[T]) -> Self {
+        debug_assert_eq!(transforms.len(), weights.len());
+
+        let pivot = match transforms.first() {
+            Some(first) => real(first),
+            None => return Self::identity(),
+        };
+
+        let mut sum = [T::ZERO; 8];
+        for (d, &w) in transforms.iter().zip(weights.iter()) {
+            let w = if dot4(&real(d), &pivot) < T::ZERO {
+                -w
+            } else {
+                w
+            };
+            for (s, &x) in sum.iter_mut().zip(d.iter()) {
+                *s += x * w;
+            }
+        }
+        sum.normalize()
+    }
+
+    #[cfg(feature = "Matrix4")]
+    fn from_mat4(m: &Mat4<T>) -> Self {
+        Self::from_rotation_translation(&Quat::from_mat4(m).normalize(), &m[12..15])
+    }
+
+    #[cfg(feature = "Matrix4")]
+    fn to_mat4(&self) -> Mat4<T> {
+        let mut m = real(self).to_mat4();
+        let t = self.translation();
+        m[12] = t[0];
+        m[13] = t[1];
+        m[14] = t[2];
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq;
+    use std::f32;
+
+    fn sample() -> DualQuat {
+        let q = Quat::from_axis_angle(&[1., 2., -1.], 0.8);
+        DualQuat::from_rotation_translation(&q, &[3., -5., 7.])
+    }
+
+    #[test]
+    fn dual_quat_transform_point() {
+        let q: Quat = Quat::from_axis_angle(&[0., 0., 1.], f32::consts::FRAC_PI_2);
+        let d = DualQuat::from_rotation_translation(&q, &[1., 2., 3.]);
+
+        assert!(almost_eq(&d.transform_point(&[1., 0., 0.]), &[1., 3., 3.]));
+        assert!(almost_eq(&d.translation(), &[1., 2., 3.]));
+        assert_eq!(d.rotation(), q);
+    }
+
+    #[test]
+    fn dual_quat_mul_conjugate() {
+        let a = sample();
+        let b = DualQuat::from_rotation_translation(
+            &Quat::from_axis_angle(&[0., 1., 0.], -1.3),
+            &[-1., 0.5, 2.],
+        );
+
+        let p = [0.3, -2., 4.];
+        let ab = a.mul_dual_quat(&b);
+        assert!(almost_eq(
+            &ab.transform_point(&p),
+            &a.transform_point(&b.transform_point(&p))
+        ));
+        assert!(almost_eq(
+            &a.mul_dual_quat(&a.conjugate()),
+            &DualQuat::identity()
+        ));
+    }
+
+    #[test]
+    fn dual_quat_normalize() {
+        let a = sample();
+        let mut scaled = a;
+        for x in &mut scaled {
+            *x *= 3.;
+        }
+        assert!(almost_eq(&scaled.normalize(), &a));
+    }
+
+    #[test]
+    fn dual_quat_sclerp() {
+        let a: DualQuat = DualQuat::from_rotation_translation(
+            &Quat::from_axis_angle(&[0., 0., 1.], 0.2),
+            &[1., 0., 0.],
+        );
+        let b = DualQuat::from_rotation_translation(
+            &Quat::from_axis_angle(&[0., 0., 1.], 1.2),
+            &[1., 0., 4.],
+        );
+
+        assert!(almost_eq(&a.sclerp(&b, 0.), &a));
+        assert!(almost_eq(&a.sclerp(&b, 1.), &b));
+
+        // a screw motion along the Z-axis
+        let mid = a.sclerp(&b, 0.5);
+        let expected = Quat::from_axis_angle(&[0., 0., 1.], 0.7);
+        assert!(almost_eq(&mid.rotation(), &expected));
+        assert!(almost_eq(&mid.translation()[2..], &[2.]));
+
+        // pure translations
+        let c = DualQuat::from_rotation_translation(&Quat::identity(), &[0., 2., 0.]);
+        let d: DualQuat = DualQuat::identity();
+        assert!(almost_eq(&d.sclerp(&c, 0.25).translation(), &[0., 0.5, 0.]));
+
+        // the other hemisphere is the same transformation
+        let mut flipped = b;
+        for x in &mut flipped {
+            *x = -*x;
+        }
+        assert!(almost_eq(&a.sclerp(&flipped, 0.5), &mid));
+    }
+
+    #[test]
+    fn dual_quat_blend() {
+        let a = sample();
+        let mut flipped = a;
+        for x in &mut flipped {
+            *x = -*x;
+        }
+        assert!(almost_eq(&DualQuat::blend(&[a, flipped], &[0.3, 0.7]), &a));
+
+        let b = DualQuat::from_rotation_translation(&Quat::identity(), &[0., 2., 0.]);
+        let c = DualQuat::from_rotation_translation(&Quat::identity(), &[0., 0., 4.]);
+        let blended = DualQuat::blend(&[b, c], &[0.5, 0.5]);
+        assert!(almost_eq(&blended.translation(), &[0., 1., 2.]));
+
+        assert_eq!(
+            DualQuat::<f32>::blend(&[], &[]),
+            DualQuat::<f32>::identity()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix4")]
+    fn dual_quat_mat4_round_trip() {
+        use crate::matrix::Matrix;
+
+        let mut m: Mat4 = Mat4::identity();
+        m.translate(&[3., -5., 7.]).rotate(0.8, &[1., 2., -1.]);
+
+        let d = DualQuat::from_mat4(&m);
+        assert!(almost_eq(&d.to_mat4(), &m));
+
+        let p = [0.3, -2., 4.];
+        assert!(almost_eq(
+            &d.transform_point(&p)[..],
+            &m.mul_vector_left(&p)[..3]
+        ));
+    }
+}
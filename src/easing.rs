@@ -0,0 +1,564 @@
+//! Easing functions for transitions and tweens.
+//!
+//! The functions map the linear progress `t` in `[0, 1]` to the eased progress, which is
+//! `0` at `t = 0` and `1` at `t = 1`. The `back` and `elastic` functions overshoot in between.
+//! The eased progress can be used to interpolate scalars and vectors (see `Vector::lerp`):
+//!
+//! ```rust
+//! use webgl_matrix::easing::{self, CubicBezier, Easing};
+//! use webgl_matrix::utils;
+//!
+//! let t = easing::quad_in_out(0.25);
+//! assert_eq!(utils::lerp(10., 20., t), 11.25);
+//!
+//! // select the easing at runtime, e.g. from a configuration
+//! let easing: Easing = Easing::CubicBezier(CubicBezier::ease_in_out());
+//! assert!((easing.apply(0.5) - 0.5).abs() < 1e-6);
+//! assert_eq!(Easing::BounceOut.apply(1.), 1.);
+//! ```
+//!
+//! The `in` variants start slowly, the `out` variants end slowly, and the `in_out` variants
+//! are composed of the `in` variant in the first half and the `out` variant in the second half.
+//! Like in Penner's reference, `back_in_out` overshoots more and `elastic_in_out` oscillates
+//! with a longer period than this composition.
+
+use crate::scalar::Scalar;
+use crate::utils::clamp;
+
+/// The overshoot of the `back` easing functions, about 10%
+const BACK_OVERSHOOT: f32 = 1.70158;
+
+/// The scale of the overshoot of `back_in_out`
+const BACK_IN_OUT_SCALE: f32 = 1.525;
+
+/// The maximum number of Newton iterations when solving a `CubicBezier`
+const MAX_NEWTON_ITERATIONS: usize = 8;
+
+/// The maximum number of bisection steps when solving a `CubicBezier`
+const MAX_BISECTION_STEPS: usize = 64;
+
+// Generates the `out` and `in_out` variants of the given `in` easing function
+macro_rules! impl_easing {
+    ($name:literal, $ease_in:ident, $ease_out:ident) => {
+        #[doc = concat!($name, " easing, decelerating to zero velocity")]
+        pub fn $ease_out<T: Scalar>(t: T) -> T {
+            T::ONE - $ease_in(T::ONE - t)
+        }
+    };
+    ($name:literal, $ease_in:ident, $ease_out:ident, $ease_in_out:ident) => {
+        impl_easing!($name, $ease_in, $ease_out);
+
+        #[doc = concat!($name, " easing, accelerating until halfway, then decelerating")]
+        pub fn $ease_in_out<T: Scalar>(t: T) -> T {
+            let two = T::from_f32(2.);
+            if t < T::from_f32(0.5) {
+                $ease_in(two * t) / two
+            } else {
+                T::ONE - $ease_in(two - two * t) / two
+            }
+        }
+    };
+}
+
+/// No easing, i.e. constant velocity
+pub fn linear<T: Scalar>(t: T) -> T {
+    t
+}
+
+/// Sinusoidal easing, accelerating from zero velocity
+pub fn sine_in<T: Scalar>(t: T) -> T {
+    let (_, cos) = (t * T::from_f32(std::f32::consts::FRAC_PI_2)).sin_cos();
+    T::ONE - cos
+}
+impl_easing!("Sinusoidal", sine_in, sine_out, sine_in_out);
+
+/// Quadratic easing, accelerating from zero velocity
+pub fn quad_in<T: Scalar>(t: T) -> T {
+    t * t
+}
+impl_easing!("Quadratic", quad_in, quad_out, quad_in_out);
+
+/// Cubic easing, accelerating from zero velocity
+pub fn cubic_in<T: Scalar>(t: T) -> T {
+    t * t * t
+}
+impl_easing!("Cubic", cubic_in, cubic_out, cubic_in_out);
+
+/// Quartic easing, accelerating from zero velocity
+pub fn quart_in<T: Scalar>(t: T) -> T {
+    let t2 = t * t;
+    t2 * t2
+}
+impl_easing!("Quartic", quart_in, quart_out, quart_in_out);
+
+/// Quintic easing, accelerating from zero velocity
+pub fn quint_in<T: Scalar>(t: T) -> T {
+    let t2 = t * t;
+    t2 * t2 * t
+}
+impl_easing!("Quintic", quint_in, quint_out, quint_in_out);
+
+/// Exponential easing, accelerating from zero velocity
+pub fn expo_in<T: Scalar>(t: T) -> T {
+    if t <= T::ZERO {
+        T::ZERO
+    } else {
+        let ten = T::from_f32(10.);
+        T::from_f32(2.).powf(ten * t - ten)
+    }
+}
+impl_easing!("Exponential", expo_in, expo_out, expo_in_out);
+
+/// Circular easing, accelerating from zero velocity
+pub fn circ_in<T: Scalar>(t: T) -> T {
+    T::ONE - (T::ONE - t * t).sqrt()
+}
+impl_easing!("Circular", circ_in, circ_out, circ_in_out);
+
+fn back<T: Scalar>(t: T, overshoot: T) -> T {
+    t * t * ((overshoot + T::ONE) * t - overshoot)
+}
+
+/// Back easing, pulling back by about 10% before accelerating
+pub fn back_in<T: Scalar>(t: T) -> T {
+    back(t, T::from_f32(BACK_OVERSHOOT))
+}
+impl_easing!("Back", back_in, back_out);
+
+/// Back easing, pulling back and overshooting by about 10% around the acceleration
+pub fn back_in_out<T: Scalar>(t: T) -> T {
+    let two = T::from_f32(2.);
+    let overshoot = T::from_f32(BACK_OVERSHOOT * BACK_IN_OUT_SCALE);
+    if t < T::from_f32(0.5) {
+        back(two * t, overshoot) / two
+    } else {
+        T::ONE - back(two - two * t, overshoot) / two
+    }
+}
+
+/// Elastic easing, oscillating with growing amplitude before accelerating
+pub fn elastic_in<T: Scalar>(t: T) -> T {
+    if t <= T::ZERO {
+        T::ZERO
+    } else if t >= T::ONE {
+        T::ONE
+    } else {
+        let ten = T::from_f32(10.);
+        let (sin, _) = ((ten * t - T::from_f32(10.75))
+            * T::from_f32(2. * std::f32::consts::FRAC_PI_3))
+        .sin_cos();
+        -T::from_f32(2.).powf(ten * t - ten) * sin
+    }
+}
+impl_easing!("Elastic", elastic_in, elastic_out);
+
+/// Elastic easing, oscillating with growing and then shrinking amplitude around the acceleration
+pub fn elastic_in_out<T: Scalar>(t: T) -> T {
+    if t <= T::ZERO {
+        T::ZERO
+    } else if t >= T::ONE {
+        T::ONE
+    } else {
+        let two = T::from_f32(2.);
+        let twenty = T::from_f32(20.);
+        let (sin, _) = ((twenty * t - T::from_f32(11.125))
+            * T::from_f32(2. * std::f32::consts::PI / 4.5))
+        .sin_cos();
+        if t < T::from_f32(0.5) {
+            -two.powf(twenty * t - T::from_f32(10.)) * sin / two
+        } else {
+            two.powf(T::from_f32(10.) - twenty * t) * sin / two + T::ONE
+        }
+    }
+}
+
+/// Bouncing easing, bouncing with growing height before accelerating
+pub fn bounce_in<T: Scalar>(t: T) -> T {
+    let c = |x: f32| T::from_f32(x);
+    let n = c(7.5625);
+    let d = c(2.75);
+
+    // the bounces of `bounce_out`, mirrored
+    let t = T::ONE - t;
+    let out = if t < T::ONE / d {
+        n * t * t
+    } else if t < c(2.) / d {
+        let t = t - c(1.5) / d;
+        n * t * t + c(0.75)
+    } else if t < c(2.5) / d {
+        let t = t - c(2.25) / d;
+        n * t * t + c(0.9375)
+    } else {
+        let t = t - c(2.625) / d;
+        n * t * t + c(0.984375)
+    };
+    T::ONE - out
+}
+impl_easing!("Bouncing", bounce_in, bounce_out, bounce_in_out);
+
+/// A timing function defined by a cubic Bézier curve, same as the CSS `cubic-bezier()` function
+///
+/// The curve starts at `(0, 0)`, ends at `(1, 1)` and is shaped by the two control points
+/// `(x1, y1)` and `(x2, y2)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier<T = f32> {
+    x1: T,
+    y1: T,
+    x2: T,
+    y2: T,
+}
+
+// The polynomial coefficients of one coordinate of the curve
+fn coefficients<T: Scalar>(p1: T, p2: T) -> (T, T, T) {
+    let three = T::from_f32(3.);
+    let c = three * p1;
+    let b = three * (p2 - p1) - c;
+    let a = T::ONE - c - b;
+    (a, b, c)
+}
+
+fn polynomial<T: Scalar>((a, b, c): (T, T, T), s: T) -> T {
+    ((a * s + b) * s + c) * s
+}
+
+fn polynomial_derivative<T: Scalar>((a, b, c): (T, T, T), s: T) -> T {
+    (T::from_f32(3.) * a * s + T::from_f32(2.) * b) * s + c
+}
+
+impl<T: Scalar> CubicBezier<T> {
+    /// Create a timing function with the given control points
+    ///
+    /// Returns `None` if `x1` or `x2` is outside of `[0, 1]`, which is invalid in CSS as well.
+    pub fn new(x1: T, y1: T, x2: T, y2: T) -> Option<Self> {
+        let valid = |x: T| x >= T::ZERO && x <= T::ONE;
+        if valid(x1) && valid(x2) {
+            Some(CubicBezier { x1, y1, x2, y2 })
+        } else {
+            None
+        }
+    }
+
+    // The presets are valid by definition
+    fn preset(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        let c = |x: f32| T::from_f32(x);
+        CubicBezier {
+            x1: c(x1),
+            y1: c(y1),
+            x2: c(x2),
+            y2: c(y2),
+        }
+    }
+
+    /// The CSS `ease` timing function, `cubic-bezier(0.25, 0.1, 0.25, 1)`
+    pub fn ease() -> Self {
+        Self::preset(0.25, 0.1, 0.25, 1.)
+    }
+
+    /// The CSS `ease-in` timing function, `cubic-bezier(0.42, 0, 1, 1)`
+    pub fn ease_in() -> Self {
+        Self::preset(0.42, 0., 1., 1.)
+    }
+
+    /// The CSS `ease-out` timing function, `cubic-bezier(0, 0, 0.58, 1)`
+    pub fn ease_out() -> Self {
+        Self::preset(0., 0., 0.58, 1.)
+    }
+
+    /// The CSS `ease-in-out` timing function, `cubic-bezier(0.42, 0, 0.58, 1)`
+    pub fn ease_in_out() -> Self {
+        Self::preset(0.42, 0., 0.58, 1.)
+    }
+
+    /// The control points `[x1, y1, x2, y2]`
+    pub fn control_points(&self) -> [T; 4] {
+        [self.x1, self.y1, self.x2, self.y2]
+    }
+
+    // The curve parameter for the given x coordinate in `[0, 1]`
+    fn solve(&self, x: T) -> T {
+        let cx = coefficients(self.x1, self.x2);
+
+        // Newton's method converges quickly for most curves
+        let mut s = x;
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let error = polynomial(cx, s) - x;
+            if error.abs() <= T::EPSILON {
+                return s;
+            }
+            let slope = polynomial_derivative(cx, s);
+            if slope.abs() <= T::EPSILON {
+                break;
+            }
+            s -= error / slope;
+        }
+
+        // fall back to bisection, x is monotonic in the curve parameter
+        let (mut lo, mut hi) = (T::ZERO, T::ONE);
+        let mut s = x;
+        for _ in 0..MAX_BISECTION_STEPS {
+            let error = polynomial(cx, s) - x;
+            if error.abs() <= T::EPSILON {
+                break;
+            }
+            if error > T::ZERO {
+                hi = s;
+            } else {
+                lo = s;
+            }
+            s = (lo + hi) / T::from_f32(2.);
+        }
+        s
+    }
+
+    /// Compute the eased progress for the given linear progress `t`
+    ///
+    /// Like in CSS, the curve is extended linearly outside of `[0, 1]` using the tangent at the
+    /// closest end.
+    pub fn apply(&self, t: T) -> T {
+        let (o, l) = (T::ZERO, T::ONE);
+        if t < o {
+            let slope = if self.x1 > o {
+                self.y1 / self.x1
+            } else if self.y1 == o && self.x2 > o {
+                self.y2 / self.x2
+            } else {
+                o
+            };
+            return slope * t;
+        }
+        if t > l {
+            let slope = if self.x2 < l {
+                (self.y2 - l) / (self.x2 - l)
+            } else if self.y2 == l && self.x1 < l {
+                (self.y1 - l) / (self.x1 - l)
+            } else {
+                o
+            };
+            return l + slope * (t - l);
+        }
+
+        let s = self.solve(t);
+        polynomial(coefficients(self.y1, self.y2), s)
+    }
+}
+
+/// An easing function which can be selected at runtime
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing<T = f32> {
+    Linear,
+    SineIn,
+    SineOut,
+    SineInOut,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    CubicBezier(CubicBezier<T>),
+}
+
+impl<T: Scalar> Easing<T> {
+    /// Compute the eased progress for the given linear progress `t`
+    pub fn apply(&self, t: T) -> T {
+        match self {
+            Easing::Linear => linear(t),
+            Easing::SineIn => sine_in(t),
+            Easing::SineOut => sine_out(t),
+            Easing::SineInOut => sine_in_out(t),
+            Easing::QuadIn => quad_in(t),
+            Easing::QuadOut => quad_out(t),
+            Easing::QuadInOut => quad_in_out(t),
+            Easing::CubicIn => cubic_in(t),
+            Easing::CubicOut => cubic_out(t),
+            Easing::CubicInOut => cubic_in_out(t),
+            Easing::QuartIn => quart_in(t),
+            Easing::QuartOut => quart_out(t),
+            Easing::QuartInOut => quart_in_out(t),
+            Easing::QuintIn => quint_in(t),
+            Easing::QuintOut => quint_out(t),
+            Easing::QuintInOut => quint_in_out(t),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => expo_out(t),
+            Easing::ExpoInOut => expo_in_out(t),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => circ_out(t),
+            Easing::CircInOut => circ_in_out(t),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => back_out(t),
+            Easing::BackInOut => back_in_out(t),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => elastic_out(t),
+            Easing::ElasticInOut => elastic_in_out(t),
+            Easing::BounceIn => bounce_in(t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => bounce_in_out(t),
+            Easing::CubicBezier(bezier) => bezier.apply(t),
+        }
+    }
+
+    /// Same as `apply`, but with `t` clamped to `[0, 1]`
+    pub fn apply_clamped(&self, t: T) -> T {
+        self.apply(clamp(t, T::ZERO, T::ONE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq_relative;
+
+    const ALL: [Easing; 31] = [
+        Easing::Linear,
+        Easing::SineIn,
+        Easing::SineOut,
+        Easing::SineInOut,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::QuartIn,
+        Easing::QuartOut,
+        Easing::QuartInOut,
+        Easing::QuintIn,
+        Easing::QuintOut,
+        Easing::QuintInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::CircIn,
+        Easing::CircOut,
+        Easing::CircInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    fn close(a: f32, b: f32) -> bool {
+        almost_eq_relative(&[a], &[b], 1e-5, 1e-5)
+    }
+
+    #[test]
+    fn easing_end_points() {
+        for easing in &ALL {
+            assert!(close(easing.apply(0.), 0.), "{:?}", easing);
+            assert!(close(easing.apply(1.), 1.), "{:?}", easing);
+            if !matches!(easing, Easing::Linear) {
+                // the `in_out` variants are symmetric
+                let sum = easing.apply(0.3) + easing.apply(0.7);
+                let name = format!("{:?}", easing);
+                assert_eq!(close(sum, 1.), name.ends_with("InOut"), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn easing_values() {
+        // reference values of the common easing implementations
+        assert!(close(quad_in(0.5), 0.25));
+        assert!(close(cubic_out(0.5), 0.875));
+        assert!(close(quart_in_out(0.25), 0.03125));
+        assert!(close(quint_in(0.5), 0.03125));
+        assert!(close(sine_in_out(0.25), 0.146_446_6));
+        assert!(close(expo_in(0.5), 0.031_25));
+        assert!(close(circ_out(0.5), 0.866_025_4));
+        assert!(close(back_in(0.5), -0.087_697_5));
+        assert!(close(elastic_out(0.25), 0.911_611_7));
+        assert!(close(bounce_out(0.5), 0.765_625));
+        assert!(close(bounce_in_out(0.75), 0.882_812_5));
+        assert!(close(back_in_out(0.25), -0.099_681_84));
+        assert!(close(back_in_out(0.6), 0.910_074_2));
+        assert!(close(elastic_in_out(0.25), 0.011_969_44));
+        assert!(close(elastic_in_out(0.6), 1.117_461_6));
+        assert_eq!(elastic_in_out(0.5f32), 0.5);
+
+        // `back` and `elastic` overshoot, `bounce` does not
+        assert!(back_out(0.7) > 1.);
+        assert!(elastic_in(0.9) < 0.);
+        for i in 0..=100 {
+            let y = bounce_out(i as f32 / 100.);
+            assert!((0. ..=1.).contains(&y));
+        }
+    }
+
+    #[test]
+    fn easing_cubic_bezier() {
+        assert_eq!(CubicBezier::new(-0.1, 0., 1., 1.), None);
+        assert_eq!(CubicBezier::new(0., 0., 1.1, 1.), None);
+
+        // equal control points on the diagonal result in a line
+        let line = CubicBezier::new(0.3, 0.3, 0.7, 0.7).unwrap();
+        for &t in &[0., 0.2, 0.5, 0.9, 1.] {
+            assert!(close(line.apply(t), t));
+        }
+
+        // reference values, computed by bisection
+        let ease: CubicBezier = CubicBezier::ease();
+        assert!(close(ease.apply(0.25), 0.408_510_6));
+        assert!(close(ease.apply(0.5), 0.802_403_4));
+        let ease_in: CubicBezier = CubicBezier::ease_in();
+        assert!(close(ease_in.apply(0.5), 0.315_356_8));
+        assert!(close(
+            CubicBezier::ease_out().apply(0.5),
+            1. - ease_in.apply(0.5)
+        ));
+
+        // steep curves which require bisection
+        let steep = CubicBezier::new(0., 1., 0., 1.).unwrap();
+        assert!(steep.apply(0.01) > 0.3);
+        assert!(close(steep.apply(1.), 1.));
+
+        // linear extrapolation outside of [0, 1]
+        let overshoot = CubicBezier::new(0.5, -0.5, 0.5, 1.5).unwrap();
+        assert!(close(overshoot.apply(-1.), 1.));
+        assert!(close(overshoot.apply(2.), 0.));
+        assert!(close(ease.apply(-1.), -0.4));
+        assert!(close(ease.apply(2.), 1.));
+    }
+
+    #[test]
+    fn easing_apply_clamped() {
+        assert_eq!(Easing::QuadIn.apply(2.), 4.);
+        assert_eq!(Easing::QuadIn.apply_clamped(2.), 1.);
+        assert_eq!(Easing::<f64>::BackIn.apply_clamped(-1.), 0.);
+    }
+
+    #[cfg(feature = "Vector3")]
+    #[test]
+    fn easing_vector_lerp() {
+        use crate::vector::Vector;
+
+        let from = [0., 2., -1.];
+        let to = [4., 2., 3.];
+        assert_eq!(from.lerp(&to, quad_out(0.5)), [3., 2., 2.]);
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(any(feature = "Matrix4", feature = "Quaternion"))]
+use crate::scalar::Scalar;
+#[cfg(any(feature = "Matrix4", feature = "Quaternion"))]
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+/// The errors of the checked (`try_*`) operations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// A slice operand has the wrong number of elements
+    ///
+    /// For homogenous operands, `expected` is the length including the homogenous coordinate.
+    DimensionMismatch { expected: usize, found: usize },
+    /// The matrix cannot be inverted
+    Singular,
+    /// A vector which has to be normalized, e.g. a rotation axis, has (almost) zero length
+    ZeroLength,
+    /// A sequence which has to be ascending, e.g. the times of keyframes, is not or contains NaN
+    NotAscending,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MathError::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimension mismatch: expected {} elements, found {}",
+                expected, found
+            ),
+            MathError::Singular => f.write_str("matrix is singular"),
+            MathError::ZeroLength => f.write_str("vector has zero length"),
+            MathError::NotAscending => f.write_str("values are not ascending"),
+        }
+    }
+}
+
+impl Error for MathError {}
+
+// Checks that `len` is `expected`
+pub(crate) fn check_len(len: usize, expected: usize) -> Result<(), MathError> {
+    if len == expected {
+        Ok(())
+    } else {
+        Err(MathError::DimensionMismatch {
+            expected,
+            found: len,
+        })
+    }
+}
+
+// Checks that `len` is `expected`, or one less if the homogenous coordinate is omitted
+pub(crate) fn check_homogenous_len(len: usize, expected: usize) -> Result<(), MathError> {
+    if len == expected || len + 1 == expected {
+        Ok(())
+    } else {
+        Err(MathError::DimensionMismatch {
+            expected,
+            found: len,
+        })
+    }
+}
+
+// Checks that the given length is larger than `threshold`, which is not the case for NaNs
+#[cfg(any(feature = "Matrix4", feature = "Quaternion"))]
+pub(crate) fn check_nonzero<T: Scalar>(len: T, threshold: T) -> Result<(), MathError> {
+    match len.partial_cmp(&threshold) {
+        Some(Ordering::Greater) => Ok(()),
+        _ => Err(MathError::ZeroLength),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "SliceOps")]
+    fn math_error_slice_ops() {
+        use crate::slice_ops::{try_add, try_dot};
+
+        let mut a = [1f32, 2., 3.];
+        assert_eq!(
+            try_add(&mut a, &[1., 1.]),
+            Err(MathError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(a, [1., 2., 3.]);
+        assert_eq!(try_add(&mut a, &[1., 1., 1.]), Ok(()));
+        assert_eq!(a, [2., 3., 4.]);
+        assert_eq!(try_dot(&a, &[1., 0., 0.]), Ok(2.));
+    }
+
+    #[test]
+    #[cfg(feature = "Vector3")]
+    fn math_error_vector() {
+        use crate::{Vec3, Vector};
+
+        let v: Vec3 = [1., 2., 3.];
+        assert_eq!(v.try_sub(&[1., 1., 1.]), Ok([0., 1., 2.]));
+        assert_eq!(
+            v.try_mul(&[1., 1., 1., 1.]),
+            Err(MathError::DimensionMismatch {
+                expected: 3,
+                found: 4
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix4")]
+    fn math_error_mat4() {
+        use crate::{Mat4, Matrix};
+
+        let mut m: Mat4 = Mat4::identity();
+        assert_eq!(m.try_mul_vector(&[1., 2., 3.]), Ok([1., 2., 3., 1.]));
+        assert_eq!(
+            m.try_mul_vector_left(&[1., 2.]),
+            Err(MathError::DimensionMismatch {
+                expected: 4,
+                found: 2
+            })
+        );
+        assert!(m.try_translate(&[1., 2., 3., 1., 0.]).is_err());
+        assert_eq!(m.try_rotate(1., &[0., 0., 0.]), Err(MathError::ZeroLength));
+        assert_eq!(
+            m.try_rotate(1., &[0., 0., 1., 0.]),
+            Err(MathError::ZeroLength)
+        );
+        assert_eq!(
+            m.try_rotate(1., &[f32::NAN, 0., 1.]),
+            Err(MathError::ZeroLength)
+        );
+        assert_eq!(m, Mat4::<f32>::identity());
+        assert!(m.try_rotate(1., &[0., 0., 1.]).is_ok());
+
+        assert_eq!(Mat4::<f32>::zeros().try_inverse(), Err(MathError::Singular));
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix3")]
+    fn math_error_mat3() {
+        use crate::{Mat3, Matrix};
+
+        let mut m: Mat3 = Mat3::identity();
+        assert_eq!(m.try_mul_vector(&[1., 2.]), Ok([1., 2., 1.]));
+        assert!(m.try_translate(&[1.]).is_err());
+        assert!(m.try_inverse().is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "Quaternion")]
+    fn math_error_quat() {
+        use crate::{Quat, Quaternion};
+
+        assert_eq!(
+            Quat::<f32>::try_from_axis_angle(&[0., 0., 0.], 1.),
+            Err(MathError::ZeroLength)
+        );
+        let q: Quat = Quat::try_from_axis_angle(&[0., 0., 1.], 1.).unwrap();
+        assert!(q.try_rotate_vector(&[1., 0.]).is_err());
+    }
+
+    #[test]
+    fn math_error_display() {
+        assert_eq!(
+            MathError::DimensionMismatch {
+                expected: 4,
+                found: 2
+            }
+            .to_string(),
+            "dimension mismatch: expected 4 elements, found 2"
+        );
+    }
+}
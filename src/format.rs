@@ -0,0 +1,367 @@
+//! Pretty-printing and parsing of matrices.
+//!
+//! Matrices are printed as aligned grids. By default every line is one row of the matrix
+//! in the usual mathematical notation, i.e. the transposed storage order:
+//!
+//! ```rust
+//! use webgl_matrix::format::{self, MatrixFormat};
+//! use webgl_matrix::{Mat4, Matrix};
+//!
+//! let mut m: Mat4 = Mat4::identity();
+//! m.translate(&[1., 2., 3.]);
+//!
+//! let text = MatrixFormat::new().with_precision(1).format(&m);
+//! assert_eq!(
+//!     text,
+//!     "[1.0, 0.0, 0.0, 1.0]\n\
+//!      [0.0, 1.0, 0.0, 2.0]\n\
+//!      [0.0, 0.0, 1.0, 3.0]\n\
+//!      [0.0, 0.0, 0.0, 1.0]\n"
+//! );
+//!
+//! let parsed: Mat4 = format::parse_mat4(&text).unwrap();
+//! assert_eq!(parsed, m);
+//! ```
+//!
+//! The parser also accepts numpy arrays (`array([[1., 0.], ...], dtype=float32)` or printed
+//! without commas) which are read row by row, as well as glm literals (`mat4(...)`,
+//! `mat4x4((...), ...)`) which are read column by column like their constructors.
+
+use crate::scalar::Scalar;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the values are arranged in the text form
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Every line is a row of the matrix (mathematical notation)
+    RowMajor,
+    /// Every line is a column of the matrix (storage order)
+    ColumnMajor,
+}
+
+/// Options for formatting and parsing matrices as grids
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatrixFormat {
+    /// The number of decimal places, `None` for the shortest exact representation
+    pub precision: Option<usize>,
+    pub layout: Layout,
+}
+
+impl Default for MatrixFormat {
+    fn default() -> Self {
+        MatrixFormat {
+            precision: None,
+            layout: Layout::RowMajor,
+        }
+    }
+}
+
+/// The reasons why a matrix cannot be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseMatrixError {
+    /// A value is not a valid number
+    InvalidNumber(String),
+    /// The number of values does not match the matrix size
+    WrongCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseMatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMatrixError::InvalidNumber(value) => write!(f, "invalid number `{}`", value),
+            ParseMatrixError::WrongCount { expected, found } => {
+                write!(f, "expected {} values, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl Error for ParseMatrixError {}
+
+// The side length of a square matrix with the given number of values
+fn dimension(len: usize) -> usize {
+    match len {
+        9 => 3,
+        16 => 4,
+        len => len,
+    }
+}
+
+// Writes the given cells as aligned grid, one line per row or column of the matrix
+pub(crate) fn write_grid(
+    f: &mut dyn fmt::Write,
+    cells: &[String],
+    layout: Layout,
+    indent: &str,
+) -> fmt::Result {
+    let n = dimension(cells.len()).max(1);
+    let lines = cells.len() / n;
+    let width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+
+    for line in 0..lines {
+        write!(f, "{}[", indent)?;
+        for i in 0..n {
+            // cells are stored column-major
+            let index = match layout {
+                Layout::RowMajor if lines > 1 => i * n + line,
+                _ => line * n + i,
+            };
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:>width$}", cells[index], width = width)?;
+        }
+        f.write_str("]\n")?;
+    }
+    Ok(())
+}
+
+impl MatrixFormat {
+    /// Create the default format (shortest representation, row-major)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given number of decimal places
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Use the given layout
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Format the given matrix (9 or 16 values) as grid
+    ///
+    /// Other numbers of values are formatted as a single line.
+    pub fn format<T: Scalar + fmt::Display>(&self, m: &[T]) -> String {
+        let mut out = String::new();
+        self.write(&mut out, m).unwrap();
+        out
+    }
+
+    fn write<T: Scalar + fmt::Display>(&self, f: &mut dyn fmt::Write, m: &[T]) -> fmt::Result {
+        let cells: Vec<String> = match self.precision {
+            Some(precision) => m.iter().map(|x| format!("{:.*}", precision, x)).collect(),
+            None => m.iter().map(|x| format!("{:?}", x)).collect(),
+        };
+        write_grid(f, &cells, self.layout, "")
+    }
+
+    /// Parse a 3x3 matrix
+    ///
+    /// Grids are read according to the layout of this format.
+    pub fn parse_mat3<T: Scalar + FromStr>(&self, s: &str) -> Result<[T; 9], ParseMatrixError> {
+        let mut out = [T::ZERO; 9];
+        self.parse_into(s, &mut out)?;
+        Ok(out)
+    }
+
+    /// Parse a 4x4 matrix
+    ///
+    /// Grids are read according to the layout of this format.
+    pub fn parse_mat4<T: Scalar + FromStr>(&self, s: &str) -> Result<[T; 16], ParseMatrixError> {
+        let mut out = [T::ZERO; 16];
+        self.parse_into(s, &mut out)?;
+        Ok(out)
+    }
+
+    fn parse_into<T: Scalar + FromStr>(
+        &self,
+        s: &str,
+        out: &mut [T],
+    ) -> Result<(), ParseMatrixError> {
+        let n = dimension(out.len());
+        let s = s.trim();
+
+        let (body, layout, glm) = if let Some(rest) = strip_glm_prefix(s) {
+            (rest, Layout::ColumnMajor, true)
+        } else if let Some(rest) = s
+            .strip_prefix("np.array")
+            .or_else(|| s.strip_prefix("array"))
+        {
+            // numpy prints rows, the dtype is not part of the values
+            (
+                rest.split("dtype").next().unwrap_or(rest),
+                Layout::RowMajor,
+                false,
+            )
+        } else if s.starts_with("[[") {
+            (s, Layout::RowMajor, false)
+        } else {
+            (s, self.layout, false)
+        };
+
+        let values = body
+            .split(|c: char| c.is_whitespace() || ",[]()".contains(c))
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                let number = token.strip_suffix('f').unwrap_or(token);
+                number
+                    .parse::<T>()
+                    .map_err(|_| ParseMatrixError::InvalidNumber(token.to_string()))
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+
+        // e.g. `mat4(1.0)` creates a diagonal matrix
+        if glm && values.len() == 1 {
+            for (i, x) in out.iter_mut().enumerate() {
+                *x = if i % (n + 1) == 0 { values[0] } else { T::ZERO };
+            }
+            return Ok(());
+        }
+
+        if values.len() != out.len() {
+            return Err(ParseMatrixError::WrongCount {
+                expected: out.len(),
+                found: values.len(),
+            });
+        }
+
+        for (i, &x) in values.iter().enumerate() {
+            let (line, j) = (i / n, i % n);
+            match layout {
+                Layout::RowMajor => out[j * n + line] = x,
+                Layout::ColumnMajor => out[i] = x,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn strip_glm_prefix(s: &str) -> Option<&str> {
+    let s = s.strip_prefix("glm::").unwrap_or(s);
+    let s = s.strip_prefix('d').unwrap_or(s);
+    ["mat4x4", "mat3x3", "mat4", "mat3"]
+        .iter()
+        .find_map(|prefix| s.strip_prefix(prefix))
+        .filter(|rest| rest.trim_start().starts_with('('))
+}
+
+/// Parse a 3x3 matrix from a row-major grid, a numpy array or a glm literal
+pub fn parse_mat3<T: Scalar + FromStr>(s: &str) -> Result<[T; 9], ParseMatrixError> {
+    MatrixFormat::default().parse_mat3(s)
+}
+
+/// Parse a 4x4 matrix from a row-major grid, a numpy array or a glm literal
+pub fn parse_mat4<T: Scalar + FromStr>(s: &str) -> Result<[T; 16], ParseMatrixError> {
+    MatrixFormat::default().parse_mat4(s)
+}
+
+// The wrappers print as grids, the precision can be given as usual, e.g. `{:.3}`
+#[cfg(all(feature = "Operators", any(feature = "Matrix3", feature = "Matrix4")))]
+macro_rules! impl_display {
+    ($type:ident) => {
+        impl<T: Scalar + fmt::Display> fmt::Display for crate::$type<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                MatrixFormat {
+                    precision: f.precision(),
+                    layout: Layout::RowMajor,
+                }
+                .write(f, &self.0)
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "Operators", feature = "Matrix3"))]
+impl_display!(M3);
+#[cfg(all(feature = "Operators", feature = "Matrix4"))]
+impl_display!(M4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const M: [f32; 16] = [
+        1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 13., 14., 15., 16.,
+    ];
+
+    #[test]
+    fn format_layouts() {
+        let format = MatrixFormat::new().with_precision(0);
+        assert_eq!(
+            format.format(&M),
+            "[ 1,  5,  9, 13]\n[ 2,  6, 10, 14]\n[ 3,  7, 11, 15]\n[ 4,  8, 12, 16]\n"
+        );
+        assert_eq!(
+            format.with_layout(Layout::ColumnMajor).format(&M[..9]),
+            "[1, 2, 3]\n[4, 5, 6]\n[7, 8, 9]\n"
+        );
+        assert_eq!(MatrixFormat::new().format(&[-0.5f64, 1.]), "[-0.5,  1.0]\n");
+    }
+
+    #[test]
+    fn format_round_trip() {
+        for &layout in &[Layout::RowMajor, Layout::ColumnMajor] {
+            let format = MatrixFormat::new().with_layout(layout);
+            let parsed: [f32; 16] = format.parse_mat4(&format.format(&M)).unwrap();
+            assert_eq!(parsed, M);
+        }
+    }
+
+    #[test]
+    fn format_parse_numpy() {
+        let numpy = "array([[ 1.,  5.,  9., 13.],
+       [ 2.,  6., 10., 14.],
+       [ 3.,  7., 11., 15.],
+       [ 4.,  8., 12., 16.]], dtype=float32)";
+        assert_eq!(parse_mat4::<f32>(numpy), Ok(M));
+
+        let printed = "[[1. 4. 7.]\n [2. 5. 8.]\n [3. 6. 9.]]";
+        assert_eq!(
+            parse_mat3::<f64>(printed),
+            Ok([1., 2., 3., 4., 5., 6., 7., 8., 9.])
+        );
+    }
+
+    #[test]
+    fn format_parse_glm() {
+        let glm = "mat4x4((1.000000, 2.000000, 3.000000, 4.000000), \
+                   (5.000000, 6.000000, 7.000000, 8.000000), \
+                   (9.000000, 10.000000, 11.000000, 12.000000), \
+                   (13.000000, 14.000000, 15.000000, 16.000000))";
+        assert_eq!(parse_mat4::<f32>(glm), Ok(M));
+
+        let constructor = "mat3(1.0f, 2.0f, 3.0f, 4.0f, 5.0f, 6.0f, 7.0f, 8.0f, 9.0f)";
+        assert_eq!(
+            parse_mat3::<f32>(constructor),
+            Ok([1., 2., 3., 4., 5., 6., 7., 8., 9.])
+        );
+
+        assert_eq!(
+            parse_mat3::<f32>("glm::dmat3(2.0)"),
+            Ok([2., 0., 0., 0., 2., 0., 0., 0., 2.])
+        );
+    }
+
+    #[test]
+    fn format_parse_errors() {
+        assert_eq!(
+            parse_mat3::<f32>("[1, 2, 3]"),
+            Err(ParseMatrixError::WrongCount {
+                expected: 9,
+                found: 3
+            })
+        );
+        assert_eq!(
+            parse_mat3::<f32>("[1, 2, x]"),
+            Err(ParseMatrixError::InvalidNumber("x".to_string()))
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    fn format_display_wrapper() {
+        let m = crate::M3([1f32, 0., 0., 0., 1., 0., 2., 3., 1.]);
+        assert_eq!(
+            format!("{:.1}", m),
+            "[1.0, 0.0, 2.0]\n[0.0, 1.0, 3.0]\n[0.0, 0.0, 1.0]\n"
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! A parent-child hierarchy of transformations, e.g. for scene graphs.
+//!
+//! Every node stores its local transformation relative to its parent. `Hierarchy::update`
+//! recomputes the world matrices of all nodes whose local transformation, or the local
+//! transformation of one of their ancestors, changed since the last update:
+//!
+//! ```rust
+//! use webgl_matrix::hierarchy::Hierarchy;
+//! use webgl_matrix::Trs;
+//!
+//! let mut scene = Hierarchy::new();
+//! let car = scene.add(Trs::identity().with_translation([10., 0., 0.]), None);
+//! let wheel = scene.add(Trs::identity().with_translation([1., -0.5, 0.]), Some(car));
+//!
+//! scene.update();
+//! assert_eq!(&scene.world(wheel)[12..15], &[11., -0.5, 0.]);
+//!
+//! scene.local_mut(car).translation = [20., 0., 0.];
+//! scene.update();
+//! assert_eq!(&scene.world(wheel)[12..15], &[21., -0.5, 0.]);
+//! ```
+
+use crate::mat4::{AffineMatrix, Mat4};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::trs::Trs;
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "Matrix3")]
+use crate::mat3::Mat3;
+
+/// The handle of a node in a `Hierarchy`
+///
+/// Handles are only valid for the hierarchy which created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The index of the node in creation order, e.g. for storing additional data per node
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// The error of `Hierarchy::set_parent` if the new parent is the node itself or one of its
+/// descendants
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleError;
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the parent is the node itself or one of its descendants")
+    }
+}
+
+impl Error for CycleError {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node<T> {
+    local: Trs<T>,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    world: Mat4<T>,
+    world_inverse: Option<Mat4<T>>,
+    dirty: bool,
+}
+
+/// An arena of nodes with local and world transformations
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hierarchy<T = f32> {
+    nodes: Vec<Node<T>>,
+    // the nodes ordered such that parents precede their children
+    order: Vec<NodeId>,
+    order_dirty: bool,
+}
+
+impl<T: Scalar> Hierarchy<T> {
+    /// Create an empty hierarchy
+    pub fn new() -> Self {
+        Hierarchy {
+            nodes: Vec::new(),
+            order: Vec::new(),
+            order_dirty: false,
+        }
+    }
+
+    /// The number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether there are no nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// All nodes in creation order
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// Add a node with the given local transformation as child of `parent`, or as root node
+    ///
+    /// The world matrix of the new node is computed by the next `update`.
+    pub fn add(&mut self, local: Trs<T>, parent: Option<NodeId>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        if let Some(parent) = parent {
+            self.nodes[parent.0].children.push(id);
+        }
+
+        self.nodes.push(Node {
+            local,
+            parent,
+            children: Vec::new(),
+            world: Mat4::identity(),
+            world_inverse: Some(Mat4::identity()),
+            dirty: true,
+        });
+        // the parent already exists, so the order stays valid
+        self.order.push(id);
+        id
+    }
+
+    /// The parent of the given node, `None` for root nodes
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    /// The children of the given node
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].children
+    }
+
+    /// Move the given node and its descendants to a new parent, or make it a root node
+    ///
+    /// The local transformation is kept, i.e. the world transformation changes.
+    /// Fails if `parent` is the node itself or one of its descendants.
+    pub fn set_parent(&mut self, id: NodeId, parent: Option<NodeId>) -> Result<(), CycleError> {
+        let mut ancestor = parent;
+        while let Some(a) = ancestor {
+            if a == id {
+                return Err(CycleError);
+            }
+            ancestor = self.nodes[a.0].parent;
+        }
+
+        if let Some(old) = self.nodes[id.0].parent {
+            self.nodes[old.0].children.retain(|&c| c != id);
+        }
+        if let Some(new) = parent {
+            self.nodes[new.0].children.push(id);
+        }
+
+        let node = &mut self.nodes[id.0];
+        node.parent = parent;
+        node.dirty = true;
+        self.order_dirty = true;
+        Ok(())
+    }
+
+    /// The local transformation of the given node, relative to its parent
+    pub fn local(&self, id: NodeId) -> &Trs<T> {
+        &self.nodes[id.0].local
+    }
+
+    /// Replace the local transformation of the given node
+    pub fn set_local(&mut self, id: NodeId, local: Trs<T>) {
+        *self.local_mut(id) = local;
+    }
+
+    /// Modify the local transformation of the given node
+    pub fn local_mut(&mut self, id: NodeId) -> &mut Trs<T> {
+        let node = &mut self.nodes[id.0];
+        node.dirty = true;
+        &mut node.local
+    }
+
+    /// Whether the world matrix of the given node itself is outdated
+    ///
+    /// Changes of its ancestors are only detected by `update`.
+    pub fn is_dirty(&self, id: NodeId) -> bool {
+        self.nodes[id.0].dirty
+    }
+
+    fn sort(&mut self) {
+        self.order.clear();
+        let mut stack: Vec<NodeId> = self
+            .nodes()
+            .filter(|id| self.nodes[id.0].parent.is_none())
+            .collect();
+        stack.reverse();
+
+        while let Some(id) = stack.pop() {
+            self.order.push(id);
+            stack.extend(self.nodes[id.0].children.iter().rev());
+        }
+        self.order_dirty = false;
+    }
+
+    /// Recompute the world matrices of all outdated nodes
+    ///
+    /// The world matrix of a node is the world matrix of its parent times its local matrix.
+    pub fn update(&mut self) {
+        if self.order_dirty {
+            self.sort();
+        }
+
+        for &id in &self.order {
+            let parent = self.nodes[id.0].parent;
+            let parent_dirty = parent.is_some_and(|p| self.nodes[p.0].dirty);
+            if !self.nodes[id.0].dirty && !parent_dirty {
+                continue;
+            }
+
+            // `a.mul(b)` computes `b * a` in column-major notation
+            let mut world = self.nodes[id.0].local.to_mat4();
+            if let Some(p) = parent {
+                let parent_world = self.nodes[p.0].world;
+                world.mul(&parent_world);
+            }
+
+            let node = &mut self.nodes[id.0];
+            let mut inverse = world;
+            node.world_inverse = inverse.inverse_affine().map(|m| *m);
+            node.world = world;
+            // mark the node, so that its children are updated as well
+            node.dirty = true;
+        }
+
+        for node in &mut self.nodes {
+            node.dirty = false;
+        }
+    }
+
+    /// The world matrix of the given node as of the last `update`
+    pub fn world(&self, id: NodeId) -> &Mat4<T> {
+        &self.nodes[id.0].world
+    }
+
+    /// The inverse of the world matrix as of the last `update`, e.g. the view matrix of a camera
+    ///
+    /// Returns `None` if the world matrix is singular, e.g. due to a zero scale.
+    pub fn world_inverse(&self, id: NodeId) -> Option<&Mat4<T>> {
+        self.nodes[id.0].world_inverse.as_ref()
+    }
+
+    /// The matrix which transforms normals into world space as of the last `update`
+    ///
+    /// This is the inverse transpose of the upper left 3x3 part of the world matrix. Returns
+    /// `None` if the world matrix is singular.
+    #[cfg(feature = "Matrix3")]
+    pub fn normal_matrix(&self, id: NodeId) -> Option<Mat3<T>> {
+        let inv = self.nodes[id.0].world_inverse.as_ref()?;
+        Some([
+            inv[0], inv[4], inv[8], inv[1], inv[5], inv[9], inv[2], inv[6], inv[10],
+        ])
+    }
+}
+
+impl<T: Scalar> Default for Hierarchy<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quat::{Quat, Quaternion};
+    use crate::utils::almost_eq;
+
+    fn translation(x: f32, y: f32, z: f32) -> Trs {
+        Trs::identity().with_translation([x, y, z])
+    }
+
+    #[test]
+    fn hierarchy_world() {
+        let mut h = Hierarchy::new();
+        let parent = h.add(
+            translation(0., 0., 5.).with_rotation(Quat::from_axis_angle(
+                &[0., 0., 1.],
+                std::f32::consts::FRAC_PI_2,
+            )),
+            None,
+        );
+        let child = h.add(translation(1., 0., 0.), Some(parent));
+        let grandchild = h.add(
+            translation(0., 2., 0.).with_scale([2., 2., 2.]),
+            Some(child),
+        );
+        h.update();
+
+        assert!(almost_eq(&h.world(child)[12..15], &[0., 1., 5.]));
+        assert!(almost_eq(&h.world(grandchild)[12..15], &[-2., 1., 5.]));
+
+        // same as composing the matrices directly
+        let mut expected = h.local(grandchild).to_mat4();
+        expected.mul(&h.local(child).to_mat4());
+        expected.mul(&h.local(parent).to_mat4());
+        assert!(almost_eq(h.world(grandchild), &expected));
+
+        assert_eq!(h.parent(grandchild), Some(child));
+        assert_eq!(h.children(parent), &[child]);
+        assert_eq!(h.len(), 3);
+    }
+
+    #[test]
+    fn hierarchy_dirty() {
+        let mut h = Hierarchy::new();
+        let a = h.add(translation(1., 0., 0.), None);
+        let b = h.add(translation(0., 1., 0.), Some(a));
+        let c = h.add(translation(0., 0., 1.), None);
+        assert!(h.is_dirty(a) && h.is_dirty(c));
+
+        h.update();
+        assert!(!h.is_dirty(a) && !h.is_dirty(b) && !h.is_dirty(c));
+
+        h.local_mut(a).translation = [2., 0., 0.];
+        assert!(h.is_dirty(a));
+        // the world matrices are only updated explicitly
+        assert_eq!(&h.world(b)[12..15], &[1., 1., 0.]);
+
+        h.update();
+        assert_eq!(&h.world(a)[12..15], &[2., 0., 0.]);
+        assert_eq!(&h.world(b)[12..15], &[2., 1., 0.]);
+        assert_eq!(&h.world(c)[12..15], &[0., 0., 1.]);
+
+        h.set_local(b, translation(0., 3., 0.));
+        h.update();
+        assert_eq!(&h.world(b)[12..15], &[2., 3., 0.]);
+    }
+
+    #[test]
+    fn hierarchy_set_parent() {
+        let mut h = Hierarchy::new();
+        let a = h.add(translation(1., 0., 0.), None);
+        let b = h.add(translation(0., 1., 0.), None);
+        let c = h.add(translation(0., 0., 1.), Some(a));
+
+        // `b` becomes a child of `c`, which was created later
+        h.set_parent(b, Some(c)).unwrap();
+        h.update();
+        assert_eq!(&h.world(b)[12..15], &[1., 1., 1.]);
+        assert_eq!(h.children(c), &[b]);
+
+        assert_eq!(h.set_parent(a, Some(b)), Err(CycleError));
+        assert_eq!(h.set_parent(a, Some(a)), Err(CycleError));
+
+        h.set_parent(c, None).unwrap();
+        h.update();
+        assert!(h.children(a).is_empty());
+        assert_eq!(&h.world(b)[12..15], &[0., 1., 1.]);
+    }
+
+    #[test]
+    fn hierarchy_world_inverse() {
+        let mut h = Hierarchy::new();
+        let a = h.add(
+            translation(1., 2., 3.).with_rotation(Quat::from_axis_angle(&[1., 1., 0.], 0.7)),
+            None,
+        );
+        let b = h.add(translation(0., 1., 0.).with_scale([1., 2., 3.]), Some(a));
+        let zero = h.add(Trs::identity().with_scale([0., 1., 1.]), Some(b));
+        h.update();
+
+        let mut product = *h.world(b);
+        product.mul(h.world_inverse(b).unwrap());
+        assert!(almost_eq(&product, &Mat4::identity()));
+        assert_eq!(h.world_inverse(zero), None);
+    }
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn hierarchy_normal_matrix() {
+        let mut h = Hierarchy::new();
+        let a = h.add(
+            Trs::identity()
+                .with_rotation(Quat::from_axis_angle(&[0., 0., 1.], 0.5))
+                .with_scale([2., 1., 1.]),
+            None,
+        );
+        h.update();
+
+        let world = h.world(a);
+        let mut expected: Mat3 = [
+            world[0], world[1], world[2], world[4], world[5], world[6], world[8], world[9],
+            world[10],
+        ];
+        expected.inverse().unwrap();
+        expected.transpose();
+        assert!(almost_eq(&h.normal_matrix(a).unwrap(), &expected));
+
+        // normals stay perpendicular to transformed tangents
+        let tangent = world.mul_vector_left(&[1., 1., 0., 0.]);
+        let normal = h.normal_matrix(a).unwrap().mul_vector_left(&[1., -1., 0.]);
+        let dot: f32 = tangent.iter().zip(normal.iter()).map(|(a, b)| a * b).sum();
+        assert!(dot.abs() < 1e-5);
+    }
+}
@@ -0,0 +1,426 @@
+pub type IVec2 = [i32; 2];
+pub type IVec3 = [i32; 3];
+pub type IVec4 = [i32; 4];
+
+pub type UVec2 = [u32; 2];
+pub type UVec3 = [u32; 3];
+pub type UVec4 = [u32; 4];
+
+pub type BVec2 = [bool; 2];
+pub type BVec3 = [bool; 3];
+pub type BVec4 = [bool; 4];
+
+/// The rounding mode used when converting floating point vectors to integer vectors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round towards zero
+    Truncate,
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards positive infinity
+    Ceil,
+    /// Round to the nearest integer, ties are rounded away from zero
+    Nearest,
+}
+
+impl Rounding {
+    #[inline]
+    fn apply(self, value: f32) -> f32 {
+        match self {
+            Rounding::Truncate => value.trunc(),
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::Nearest => value.round(),
+        }
+    }
+}
+
+/// The base trait for integer vectors
+///
+/// Like in GLSL, arithmetic wraps around on overflow.
+pub trait IntVector {
+    type VectorType;
+    type ElementType;
+    type FloatVectorType;
+
+    /// Create a vector filled with zeros
+    fn zeros() -> Self::VectorType;
+
+    /// Create a vector filled with ones
+    fn ones() -> Self::VectorType;
+
+    /// Perform element-wise multiplication with the given right-hand-side operand
+    fn mul(&self, rhs: &[Self::ElementType]) -> Self::VectorType;
+
+    /// Perform element-wise integer division with the given right-hand-side operand
+    ///
+    /// Panics if any of the divisors is zero.
+    fn div(&self, rhs: &[Self::ElementType]) -> Self::VectorType;
+
+    /// Perform element-wise addition with the given right-hand-side operand
+    fn add(&self, rhs: &[Self::ElementType]) -> Self::VectorType;
+    /// Perform element-wise substraction with the given right-hand-side operand
+    fn sub(&self, rhs: &[Self::ElementType]) -> Self::VectorType;
+
+    /// Scale the vector elment-wise by the given constant
+    fn scale(&self, factor: Self::ElementType) -> Self::VectorType;
+
+    /// Calculate the dot product of this vector and the given right-hand-side operand
+    fn dot(&self, rhs: &[Self::ElementType]) -> Self::ElementType;
+
+    /// Compute the element-wise minimum of this vector and the given right-hand-side operand
+    fn element_min(&self, rhs: &[Self::ElementType]) -> Self::VectorType;
+    /// Compute the element-wise maximum of this vector and the given right-hand-side operand
+    fn element_max(&self, rhs: &[Self::ElementType]) -> Self::VectorType;
+
+    /// Convert to a floating point vector
+    fn to_f32(&self) -> Self::FloatVectorType;
+}
+
+/// Conversion of floating point vectors to integer vectors
+pub trait ToIntVector {
+    type IVectorType;
+    type UVectorType;
+
+    /// Convert to a signed integer vector using the given rounding mode
+    ///
+    /// Values out of range are saturated, NaN is converted to 0.
+    fn to_ivec(&self, rounding: Rounding) -> Self::IVectorType;
+
+    /// Convert to an unsigned integer vector using the given rounding mode
+    ///
+    /// Values out of range (including negative values) are saturated, NaN is converted to 0.
+    fn to_uvec(&self, rounding: Rounding) -> Self::UVectorType;
+}
+
+/// Component-wise comparisons producing boolean masks
+pub trait CompareVector {
+    type MaskType;
+
+    /// Component-wise `self == rhs`
+    fn cmp_eq(&self, rhs: &Self) -> Self::MaskType;
+    /// Component-wise `self != rhs`
+    fn cmp_ne(&self, rhs: &Self) -> Self::MaskType;
+    /// Component-wise `self < rhs`
+    fn cmp_lt(&self, rhs: &Self) -> Self::MaskType;
+    /// Component-wise `self <= rhs`
+    fn cmp_le(&self, rhs: &Self) -> Self::MaskType;
+    /// Component-wise `self > rhs`
+    fn cmp_gt(&self, rhs: &Self) -> Self::MaskType;
+    /// Component-wise `self >= rhs`
+    fn cmp_ge(&self, rhs: &Self) -> Self::MaskType;
+}
+
+/// Operations on boolean masks
+pub trait BoolVector {
+    /// Returns true if any component is true
+    fn any(&self) -> bool;
+
+    /// Returns true if all components are true
+    fn all(&self) -> bool;
+
+    /// Component-wise logical negation
+    fn not(&self) -> Self;
+}
+
+/// Component-wise selection by a boolean mask
+pub trait Select<V> {
+    /// Picks the component of `if_true` where the mask is set and the
+    /// component of `if_false` otherwise
+    fn select(&self, if_true: &V, if_false: &V) -> V;
+}
+
+macro_rules! impl_int_vector {
+    ($type:ty, $elem:ty, $n:expr) => {
+        impl IntVector for $type {
+            type VectorType = $type;
+            type ElementType = $elem;
+            type FloatVectorType = [f32; $n];
+
+            fn zeros() -> $type {
+                [0; $n]
+            }
+
+            fn ones() -> $type {
+                [1; $n]
+            }
+
+            fn mul(&self, rhs: &[$elem]) -> $type {
+                let mut dst = *self;
+                for (l, &r) in dst.iter_mut().zip(rhs.iter()) {
+                    *l = l.wrapping_mul(r);
+                }
+                dst
+            }
+
+            fn div(&self, rhs: &[$elem]) -> $type {
+                let mut dst = *self;
+                for (l, &r) in dst.iter_mut().zip(rhs.iter()) {
+                    *l = l.wrapping_div(r);
+                }
+                dst
+            }
+
+            fn add(&self, rhs: &[$elem]) -> $type {
+                let mut dst = *self;
+                for (l, &r) in dst.iter_mut().zip(rhs.iter()) {
+                    *l = l.wrapping_add(r);
+                }
+                dst
+            }
+
+            fn sub(&self, rhs: &[$elem]) -> $type {
+                let mut dst = *self;
+                for (l, &r) in dst.iter_mut().zip(rhs.iter()) {
+                    *l = l.wrapping_sub(r);
+                }
+                dst
+            }
+
+            fn scale(&self, factor: $elem) -> $type {
+                let mut dst = *self;
+                for l in dst.iter_mut() {
+                    *l = l.wrapping_mul(factor);
+                }
+                dst
+            }
+
+            fn dot(&self, rhs: &[$elem]) -> $elem {
+                let mut sum: $elem = 0;
+                for (&l, &r) in self.iter().zip(rhs.iter()) {
+                    sum = sum.wrapping_add(l.wrapping_mul(r));
+                }
+                sum
+            }
+
+            fn element_min(&self, rhs: &[$elem]) -> $type {
+                let mut dst = *self;
+                for (l, &r) in dst.iter_mut().zip(rhs.iter()) {
+                    *l = (*l).min(r);
+                }
+                dst
+            }
+
+            fn element_max(&self, rhs: &[$elem]) -> $type {
+                let mut dst = *self;
+                for (l, &r) in dst.iter_mut().zip(rhs.iter()) {
+                    *l = (*l).max(r);
+                }
+                dst
+            }
+
+            fn to_f32(&self) -> [f32; $n] {
+                let mut dst = [0.; $n];
+                for (d, &s) in dst.iter_mut().zip(self.iter()) {
+                    *d = s as f32;
+                }
+                dst
+            }
+        }
+    };
+}
+
+macro_rules! impl_fixed_size_ops {
+    ($n:expr) => {
+        impl ToIntVector for [f32; $n] {
+            type IVectorType = [i32; $n];
+            type UVectorType = [u32; $n];
+
+            fn to_ivec(&self, rounding: Rounding) -> [i32; $n] {
+                let mut dst = [0; $n];
+                for (d, &s) in dst.iter_mut().zip(self.iter()) {
+                    *d = rounding.apply(s) as i32;
+                }
+                dst
+            }
+
+            fn to_uvec(&self, rounding: Rounding) -> [u32; $n] {
+                let mut dst = [0; $n];
+                for (d, &s) in dst.iter_mut().zip(self.iter()) {
+                    *d = rounding.apply(s) as u32;
+                }
+                dst
+            }
+        }
+
+        impl<T: PartialOrd> CompareVector for [T; $n] {
+            type MaskType = [bool; $n];
+
+            fn cmp_eq(&self, rhs: &Self) -> [bool; $n] {
+                let mut dst = [false; $n];
+                for (d, (l, r)) in dst.iter_mut().zip(self.iter().zip(rhs.iter())) {
+                    *d = l == r;
+                }
+                dst
+            }
+
+            fn cmp_ne(&self, rhs: &Self) -> [bool; $n] {
+                let mut dst = [false; $n];
+                for (d, (l, r)) in dst.iter_mut().zip(self.iter().zip(rhs.iter())) {
+                    *d = l != r;
+                }
+                dst
+            }
+
+            fn cmp_lt(&self, rhs: &Self) -> [bool; $n] {
+                let mut dst = [false; $n];
+                for (d, (l, r)) in dst.iter_mut().zip(self.iter().zip(rhs.iter())) {
+                    *d = l < r;
+                }
+                dst
+            }
+
+            fn cmp_le(&self, rhs: &Self) -> [bool; $n] {
+                let mut dst = [false; $n];
+                for (d, (l, r)) in dst.iter_mut().zip(self.iter().zip(rhs.iter())) {
+                    *d = l <= r;
+                }
+                dst
+            }
+
+            fn cmp_gt(&self, rhs: &Self) -> [bool; $n] {
+                let mut dst = [false; $n];
+                for (d, (l, r)) in dst.iter_mut().zip(self.iter().zip(rhs.iter())) {
+                    *d = l > r;
+                }
+                dst
+            }
+
+            fn cmp_ge(&self, rhs: &Self) -> [bool; $n] {
+                let mut dst = [false; $n];
+                for (d, (l, r)) in dst.iter_mut().zip(self.iter().zip(rhs.iter())) {
+                    *d = l >= r;
+                }
+                dst
+            }
+        }
+
+        impl BoolVector for [bool; $n] {
+            fn any(&self) -> bool {
+                self.iter().any(|&b| b)
+            }
+
+            fn all(&self) -> bool {
+                self.iter().all(|&b| b)
+            }
+
+            fn not(&self) -> Self {
+                let mut dst = *self;
+                for b in dst.iter_mut() {
+                    *b = !*b;
+                }
+                dst
+            }
+        }
+
+        impl<T: Copy> Select<[T; $n]> for [bool; $n] {
+            fn select(&self, if_true: &[T; $n], if_false: &[T; $n]) -> [T; $n] {
+                let mut dst = *if_false;
+                for ((d, &t), &mask) in dst.iter_mut().zip(if_true.iter()).zip(self.iter()) {
+                    if mask {
+                        *d = t;
+                    }
+                }
+                dst
+            }
+        }
+    };
+}
+
+impl_int_vector!(IVec2, i32, 2);
+impl_int_vector!(IVec3, i32, 3);
+impl_int_vector!(IVec4, i32, 4);
+impl_int_vector!(UVec2, u32, 2);
+impl_int_vector!(UVec3, u32, 3);
+impl_int_vector!(UVec4, u32, 4);
+
+impl_fixed_size_ops!(2);
+impl_fixed_size_ops!(3);
+impl_fixed_size_ops!(4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ivec_arithmetic() {
+        let a: IVec3 = [1, -2, 3];
+        let b = [4, 5, -6];
+
+        assert_eq!(a.add(&b), [5, 3, -3]);
+        assert_eq!(a.sub(&b), [-3, -7, 9]);
+        assert_eq!(a.mul(&b), [4, -10, -18]);
+        assert_eq!(b.div(&a), [4, -2, -2]);
+        assert_eq!(a.scale(2), [2, -4, 6]);
+        assert_eq!(a.dot(&b), 4 - 10 - 18);
+        assert_eq!(a.element_min(&b), [1, -2, -6]);
+        assert_eq!(a.element_max(&b), [4, 5, 3]);
+    }
+
+    #[test]
+    fn uvec_wraps() {
+        let a: UVec2 = [0, u32::MAX];
+
+        assert_eq!(a.sub(&[1, 0]), [u32::MAX, u32::MAX]);
+        assert_eq!(a.add(&[0, 1]), [0, 0]);
+    }
+
+    #[test]
+    fn ivec_to_f32() {
+        let a: IVec4 = [1, -2, 3, 0];
+        assert_eq!(a.to_f32(), [1., -2., 3., 0.]);
+    }
+
+    #[test]
+    fn vec_to_ivec_rounding() {
+        let a = [1.5f32, -1.5, 2.4, -2.6];
+
+        assert_eq!(a.to_ivec(Rounding::Truncate), [1, -1, 2, -2]);
+        assert_eq!(a.to_ivec(Rounding::Floor), [1, -2, 2, -3]);
+        assert_eq!(a.to_ivec(Rounding::Ceil), [2, -1, 3, -2]);
+        assert_eq!(a.to_ivec(Rounding::Nearest), [2, -2, 2, -3]);
+    }
+
+    #[test]
+    fn vec_to_uvec_saturates() {
+        let a = [-1.5f32, 3.5, f32::NAN];
+        assert_eq!(a.to_uvec(Rounding::Nearest), [0, 4, 0]);
+    }
+
+    #[test]
+    fn compare_produces_masks() {
+        let a: IVec3 = [1, 2, 3];
+        let b = [3, 2, 1];
+
+        assert_eq!(a.cmp_eq(&b), [false, true, false]);
+        assert_eq!(a.cmp_ne(&b), [true, false, true]);
+        assert_eq!(a.cmp_lt(&b), [true, false, false]);
+        assert_eq!(a.cmp_le(&b), [true, true, false]);
+        assert_eq!(a.cmp_gt(&b), [false, false, true]);
+        assert_eq!(a.cmp_ge(&b), [false, true, true]);
+
+        let v = [0.5f32, 1.5];
+        assert_eq!(v.cmp_lt(&[1., 1.]), [true, false]);
+    }
+
+    #[test]
+    fn bvec_any_all_not() {
+        let a: BVec3 = [true, false, true];
+
+        assert!(a.any());
+        assert!(!a.all());
+        assert_eq!(a.not(), [false, true, false]);
+        assert!([true; 4].all());
+        assert!(![false; 2].any());
+    }
+
+    #[test]
+    fn bvec_select() {
+        let a: IVec3 = [1, 5, 3];
+        let b = [4, 2, 6];
+
+        let mask = a.cmp_lt(&b);
+        assert_eq!(mask.select(&a, &b), a.element_min(&b));
+        assert_eq!(mask.select(&[1., 1., 1.], &[0., 0., 0.]), [1., 0., 1.]);
+    }
+}
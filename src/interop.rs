@@ -0,0 +1,462 @@
+//! Conversions from and to the types of other math libraries.
+//!
+//! Since all types of this crate are plain arrays, conversions are provided by the `Convert`
+//! trait. The newtype wrappers of the `Operators` feature additionally implement `From` and `Into`.
+//!
+//! Matrices of this crate are stored column-major, so they always map to the column-major types,
+//! e.g. `mint::ColumnMatrix4`. Quaternions are stored as `[x, y, z, w]`.
+//!
+//! ```rust
+//! # #[cfg(feature = "glam")]
+//! # {
+//! use webgl_matrix::{Convert, Mat4, Matrix};
+//!
+//! let mut m: Mat4 = Mat4::identity();
+//! m.translate(&[1., 2., 3.]);
+//!
+//! let g: glam::Mat4 = m.convert_to();
+//! assert_eq!(g.w_axis, glam::Vec4::new(1., 2., 3., 1.));
+//! assert_eq!(Mat4::convert_from(g), m);
+//! # }
+//! ```
+//!
+//! `mint` itself already implements `From` for the plain arrays, e.g.
+//! `mint::ColumnMatrix4::from(m)`. Be aware that `mint::RowMatrix4::from(m)` compiles as well,
+//! but interprets the matrix transposed.
+
+/// Conversion from and to the type `U` of another library
+pub trait Convert<U> {
+    /// Create a value from the corresponding value of the other library
+    fn convert_from(value: U) -> Self;
+
+    /// Convert to the corresponding value of the other library
+    fn convert_to(&self) -> U;
+}
+
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use super::Convert;
+
+    macro_rules! impl_mint {
+        ($n:expr, $other:ident) => {
+            impl<T: Copy> Convert<mint::$other<T>> for [T; $n] {
+                fn convert_from(value: mint::$other<T>) -> Self {
+                    value.into()
+                }
+
+                fn convert_to(&self) -> mint::$other<T> {
+                    (*self).into()
+                }
+            }
+        };
+    }
+
+    impl_mint!(2, Vector2);
+    impl_mint!(3, Vector3);
+    impl_mint!(4, Vector4);
+    impl_mint!(4, Quaternion);
+    impl_mint!(9, ColumnMatrix3);
+    impl_mint!(16, ColumnMatrix4);
+
+    #[cfg(feature = "Operators")]
+    macro_rules! impl_mint_wrapper {
+        ($wrapper:ident, $other:ident) => {
+            impl<T: Copy> From<mint::$other<T>> for crate::$wrapper<T> {
+                fn from(value: mint::$other<T>) -> Self {
+                    crate::$wrapper(value.into())
+                }
+            }
+
+            impl<T: Copy> From<crate::$wrapper<T>> for mint::$other<T> {
+                fn from(value: crate::$wrapper<T>) -> Self {
+                    value.0.into()
+                }
+            }
+        };
+    }
+
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    impl_mint_wrapper!(V3, Vector3);
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    impl_mint_wrapper!(V4, Vector4);
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    impl_mint_wrapper!(M3, ColumnMatrix3);
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    impl_mint_wrapper!(M4, ColumnMatrix4);
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::Convert;
+
+    macro_rules! impl_glam {
+        ($elem:ty, $n:expr, $other:ty, $from:ident, $to:ident) => {
+            impl Convert<$other> for [$elem; $n] {
+                fn convert_from(value: $other) -> Self {
+                    value.$to()
+                }
+
+                fn convert_to(&self) -> $other {
+                    <$other>::$from(self)
+                }
+            }
+        };
+    }
+
+    macro_rules! impl_glam_vector {
+        ($elem:ty, $n:expr, $other:ty) => {
+            impl Convert<$other> for [$elem; $n] {
+                fn convert_from(value: $other) -> Self {
+                    value.to_array()
+                }
+
+                fn convert_to(&self) -> $other {
+                    <$other>::from_array(*self)
+                }
+            }
+        };
+    }
+
+    impl_glam_vector!(f32, 2, glam::Vec2);
+    impl_glam_vector!(f32, 3, glam::Vec3);
+    impl_glam_vector!(f32, 4, glam::Vec4);
+    impl_glam_vector!(f32, 4, glam::Quat);
+    impl_glam!(f32, 9, glam::Mat3, from_cols_array, to_cols_array);
+    impl_glam!(f32, 16, glam::Mat4, from_cols_array, to_cols_array);
+
+    impl_glam_vector!(f64, 2, glam::DVec2);
+    impl_glam_vector!(f64, 3, glam::DVec3);
+    impl_glam_vector!(f64, 4, glam::DVec4);
+    impl_glam_vector!(f64, 4, glam::DQuat);
+    impl_glam!(f64, 9, glam::DMat3, from_cols_array, to_cols_array);
+    impl_glam!(f64, 16, glam::DMat4, from_cols_array, to_cols_array);
+
+    #[cfg(feature = "Operators")]
+    macro_rules! impl_glam_wrapper {
+        ($wrapper:ident, $elem:ty, $other:ty) => {
+            impl From<$other> for crate::$wrapper<$elem> {
+                fn from(value: $other) -> Self {
+                    crate::$wrapper(Convert::convert_from(value))
+                }
+            }
+
+            impl From<crate::$wrapper<$elem>> for $other {
+                fn from(value: crate::$wrapper<$elem>) -> Self {
+                    value.0.convert_to()
+                }
+            }
+        };
+    }
+
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    impl_glam_wrapper!(V3, f32, glam::Vec3);
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    impl_glam_wrapper!(V3, f64, glam::DVec3);
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    impl_glam_wrapper!(V4, f32, glam::Vec4);
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    impl_glam_wrapper!(V4, f64, glam::DVec4);
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    impl_glam_wrapper!(M3, f32, glam::Mat3);
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    impl_glam_wrapper!(M3, f64, glam::DMat3);
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    impl_glam_wrapper!(M4, f32, glam::Mat4);
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    impl_glam_wrapper!(M4, f64, glam::DMat4);
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impls {
+    use super::Convert;
+    use nalgebra::{RealField, Scalar};
+
+    macro_rules! impl_nalgebra {
+        ($n:expr, $other:ident) => {
+            impl<T: Scalar + Copy> Convert<nalgebra::$other<T>> for [T; $n] {
+                fn convert_from(value: nalgebra::$other<T>) -> Self {
+                    // nalgebra stores its matrices column-major as well
+                    let mut out = [value[0]; $n];
+                    out.copy_from_slice(value.as_slice());
+                    out
+                }
+
+                fn convert_to(&self) -> nalgebra::$other<T> {
+                    nalgebra::$other::from_column_slice(self)
+                }
+            }
+        };
+    }
+
+    impl_nalgebra!(2, Vector2);
+    impl_nalgebra!(3, Vector3);
+    impl_nalgebra!(4, Vector4);
+    impl_nalgebra!(9, Matrix3);
+    impl_nalgebra!(16, Matrix4);
+
+    impl<T: Scalar + Copy> Convert<nalgebra::Quaternion<T>> for [T; 4] {
+        fn convert_from(value: nalgebra::Quaternion<T>) -> Self {
+            // the coordinates are stored as `[i, j, k, w]`
+            let c = value.coords;
+            [c[0], c[1], c[2], c[3]]
+        }
+
+        fn convert_to(&self) -> nalgebra::Quaternion<T> {
+            nalgebra::Quaternion {
+                coords: nalgebra::Vector4::from(*self),
+            }
+        }
+    }
+
+    /// The quaternion is normalized when converting to `UnitQuaternion`
+    impl<T: RealField + Copy> Convert<nalgebra::UnitQuaternion<T>> for [T; 4] {
+        fn convert_from(value: nalgebra::UnitQuaternion<T>) -> Self {
+            Self::convert_from(value.into_inner())
+        }
+
+        fn convert_to(&self) -> nalgebra::UnitQuaternion<T> {
+            nalgebra::UnitQuaternion::new_normalize(self.convert_to())
+        }
+    }
+
+    #[cfg(feature = "Operators")]
+    macro_rules! impl_nalgebra_wrapper {
+        ($wrapper:ident, $other:ident) => {
+            impl<T: Scalar + Copy> From<nalgebra::$other<T>> for crate::$wrapper<T> {
+                fn from(value: nalgebra::$other<T>) -> Self {
+                    crate::$wrapper(Convert::convert_from(value))
+                }
+            }
+
+            impl<T: Scalar + Copy> From<crate::$wrapper<T>> for nalgebra::$other<T> {
+                fn from(value: crate::$wrapper<T>) -> Self {
+                    value.0.convert_to()
+                }
+            }
+        };
+    }
+
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    impl_nalgebra_wrapper!(V3, Vector3);
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    impl_nalgebra_wrapper!(V4, Vector4);
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    impl_nalgebra_wrapper!(M3, Matrix3);
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    impl_nalgebra_wrapper!(M4, Matrix4);
+}
+
+#[cfg(feature = "cgmath")]
+mod cgmath_impls {
+    use super::Convert;
+    use cgmath::{Matrix3, Matrix4, Quaternion, Vector2, Vector3, Vector4};
+
+    impl<T: Copy> Convert<Vector2<T>> for [T; 2] {
+        fn convert_from(v: Vector2<T>) -> Self {
+            [v.x, v.y]
+        }
+
+        fn convert_to(&self) -> Vector2<T> {
+            Vector2 {
+                x: self[0],
+                y: self[1],
+            }
+        }
+    }
+
+    impl<T: Copy> Convert<Vector3<T>> for [T; 3] {
+        fn convert_from(v: Vector3<T>) -> Self {
+            [v.x, v.y, v.z]
+        }
+
+        fn convert_to(&self) -> Vector3<T> {
+            Vector3 {
+                x: self[0],
+                y: self[1],
+                z: self[2],
+            }
+        }
+    }
+
+    impl<T: Copy> Convert<Vector4<T>> for [T; 4] {
+        fn convert_from(v: Vector4<T>) -> Self {
+            [v.x, v.y, v.z, v.w]
+        }
+
+        fn convert_to(&self) -> Vector4<T> {
+            Vector4 {
+                x: self[0],
+                y: self[1],
+                z: self[2],
+                w: self[3],
+            }
+        }
+    }
+
+    impl<T: Copy> Convert<Quaternion<T>> for [T; 4] {
+        fn convert_from(q: Quaternion<T>) -> Self {
+            [q.v.x, q.v.y, q.v.z, q.s]
+        }
+
+        fn convert_to(&self) -> Quaternion<T> {
+            Quaternion {
+                v: [self[0], self[1], self[2]].convert_to(),
+                s: self[3],
+            }
+        }
+    }
+
+    impl<T: Copy> Convert<Matrix3<T>> for [T; 9] {
+        fn convert_from(m: Matrix3<T>) -> Self {
+            let (x, y, z) = (m.x, m.y, m.z);
+            [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+        }
+
+        fn convert_to(&self) -> Matrix3<T> {
+            let m = self;
+            Matrix3 {
+                x: [m[0], m[1], m[2]].convert_to(),
+                y: [m[3], m[4], m[5]].convert_to(),
+                z: [m[6], m[7], m[8]].convert_to(),
+            }
+        }
+    }
+
+    impl<T: Copy> Convert<Matrix4<T>> for [T; 16] {
+        fn convert_from(m: Matrix4<T>) -> Self {
+            let (x, y, z, w) = (m.x, m.y, m.z, m.w);
+            [
+                x.x, x.y, x.z, x.w, y.x, y.y, y.z, y.w, z.x, z.y, z.z, z.w, w.x, w.y, w.z, w.w,
+            ]
+        }
+
+        fn convert_to(&self) -> Matrix4<T> {
+            let m = self;
+            Matrix4 {
+                x: [m[0], m[1], m[2], m[3]].convert_to(),
+                y: [m[4], m[5], m[6], m[7]].convert_to(),
+                z: [m[8], m[9], m[10], m[11]].convert_to(),
+                w: [m[12], m[13], m[14], m[15]].convert_to(),
+            }
+        }
+    }
+
+    #[cfg(feature = "Operators")]
+    macro_rules! impl_cgmath_wrapper {
+        ($wrapper:ident, $other:ident) => {
+            impl<T: Copy> From<$other<T>> for crate::$wrapper<T> {
+                fn from(value: $other<T>) -> Self {
+                    crate::$wrapper(Convert::convert_from(value))
+                }
+            }
+
+            impl<T: Copy> From<crate::$wrapper<T>> for $other<T> {
+                fn from(value: crate::$wrapper<T>) -> Self {
+                    value.0.convert_to()
+                }
+            }
+        };
+    }
+
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    impl_cgmath_wrapper!(V3, Vector3);
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    impl_cgmath_wrapper!(V4, Vector4);
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    impl_cgmath_wrapper!(M3, Matrix3);
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    impl_cgmath_wrapper!(M4, Matrix4);
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::Convert;
+
+    // A translation by (1, 2, 3) with an additional entry at row 0, column 1, in column-major order
+    #[allow(dead_code)]
+    const M: [f32; 16] = [
+        1., 0., 0., 0., 5., 1., 0., 0., 0., 0., 1., 0., 1., 2., 3., 1.,
+    ];
+
+    #[test]
+    #[cfg(feature = "mint")]
+    fn interop_mint_layout() {
+        let m: mint::ColumnMatrix4<f32> = M.convert_to();
+        assert_eq!(m.y.x, 5.);
+        assert_eq!(m.w, mint::Vector4::from([1., 2., 3., 1.]));
+        assert_eq!(<[f32; 16]>::convert_from(m), M);
+
+        let q: mint::Quaternion<f32> = [1., 2., 3., 4.].convert_to();
+        assert_eq!(q.s, 4.);
+        assert_eq!(q.v, mint::Vector3::from([1., 2., 3.]));
+    }
+
+    #[test]
+    #[cfg(feature = "glam")]
+    fn interop_glam_layout() {
+        let m: glam::Mat4 = M.convert_to();
+        assert_eq!(m.col(1).x, 5.);
+        assert_eq!(
+            m.transform_point3(glam::Vec3::ZERO),
+            glam::Vec3::new(1., 2., 3.)
+        );
+        assert_eq!(<[f32; 16]>::convert_from(m), M);
+
+        let m: glam::DMat3 = [1., 0., 0., 5., 1., 0., 0., 0., 1.].convert_to();
+        assert_eq!(m.col(1).x, 5.);
+
+        let q: glam::Quat = [0., 0., 0.6, 0.8].convert_to();
+        assert_eq!(q.w, 0.8);
+        assert_eq!(<[f32; 4]>::convert_from(q), [0., 0., 0.6, 0.8]);
+    }
+
+    #[test]
+    #[cfg(feature = "nalgebra")]
+    fn interop_nalgebra_layout() {
+        let m: nalgebra::Matrix4<f32> = M.convert_to();
+        assert_eq!(m[(0, 1)], 5.);
+        assert_eq!(
+            m.transform_point(&nalgebra::Point3::origin()),
+            nalgebra::Point3::new(1., 2., 3.)
+        );
+        assert_eq!(<[f32; 16]>::convert_from(m), M);
+
+        let q: nalgebra::Quaternion<f32> = [1., 2., 3., 4.].convert_to();
+        assert_eq!(q.w, 4.);
+        assert_eq!(q.i, 1.);
+
+        let q: nalgebra::UnitQuaternion<f32> = [0., 0., 3., 4.].convert_to();
+        assert_eq!(<[f32; 4]>::convert_from(q), [0., 0., 0.6, 0.8]);
+    }
+
+    #[test]
+    #[cfg(feature = "cgmath")]
+    fn interop_cgmath_layout() {
+        use cgmath::{EuclideanSpace, Transform};
+
+        let m: cgmath::Matrix4<f32> = M.convert_to();
+        assert_eq!(m.y.x, 5.);
+        assert_eq!(
+            m.transform_point(cgmath::Point3::origin()),
+            cgmath::Point3::new(1., 2., 3.)
+        );
+        assert_eq!(<[f32; 16]>::convert_from(m), M);
+
+        let q: cgmath::Quaternion<f32> = [1., 2., 3., 4.].convert_to();
+        assert_eq!(q.s, 4.);
+        assert_eq!(<[f32; 4]>::convert_from(q), [1., 2., 3., 4.]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "Operators", feature = "Matrix4", feature = "glam"))]
+    fn interop_wrappers() {
+        use crate::{M4, V3};
+
+        let m: glam::Mat4 = M4(M).into();
+        assert_eq!(M4::from(m), M4(M));
+
+        let v: glam::DVec3 = V3([1., 2., 3.]).into();
+        assert_eq!(V3::from(v), V3([1., 2., 3.]));
+    }
+}
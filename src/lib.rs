@@ -2,12 +2,26 @@
 //!
 //! At the core this library only consists of the traits `Matrix` and `Vector`. All implementations are optional features and can be added as needed.
 //!
+//! All types are generic over their element type (see `Scalar`), which defaults to `f32`.
+//! `f64` is supported out of the box, e.g. `Mat4<f64>`. Where the element type cannot be
+//! inferred, it has to be named explicitly, e.g. `Mat4::<f32>::identity()`.
+//!
 //! Available features:
 //! * `Matrix4`: 4x4 matrix operations (includes *Vector4*)
 //! * `Matrix3`: 3x3 matrix operations (includes *Vector3*)
 //! * `Vector4`: 4-dimensional vector operations
 //! * `Vector3`: 3-dimensional vector operations
+//! * `Vector2`: 2-dimensional vector operations
+//! * `Quaternion`: Quaternion rotations, dual quaternions, keyframe animations, the `Trs` transformation and transform hierarchies (includes *Vector3*)
 //! * `SliceOps`: Low level slice operations such as addition, subtraction, scaling etc.
+//! * `IntVector`: Integer and boolean vectors (`IVec*`, `UVec*`, `BVec*`) and component-wise comparisons
+//! * `Operators`: Newtype wrappers (`V3`, `V4`, `M3`, `M4`) with overloaded operators
+//! * `serde`: Helpers for (de)serializing matrices and quaternions in structured forms
+//! * `bytemuck`: `Pod` and `Zeroable` implementations for the newtype wrappers and `Trs`
+//! * `wasm-bindgen`: JavaScript bindings for `Float32Array`s in the calling style of gl-matrix (includes *Matrix4* and *Matrix3*)
+//! * `mint`, `glam`, `nalgebra`, `cgmath`: Conversions from and to the types of these libraries (see `Convert`)
+//! * `approx`: `AbsDiffEq`, `RelativeEq` and `UlpsEq` implementations for the newtype wrappers and `Trs`
+//! * `rand`: Distributions of random directions, points in shapes and rotations (see `random`)
 //!
 //! ## Examples
 //!
@@ -50,19 +64,26 @@
 //! }
 //! ```
 
+mod error;
 mod matrix;
+mod scalar;
 #[macro_use]
 mod vector;
 
+#[cfg(feature = "Vector2")]
+mod vec2;
+#[cfg(feature = "Vector2")]
+pub use vec2::Vec2;
+
 #[cfg(feature = "Vector3")]
 mod vec3;
 #[cfg(feature = "Vector3")]
-pub use vec3::Vec3;
+pub use vec3::{CrossProduct, Vec3};
 
 #[cfg(feature = "Matrix3")]
 mod mat3;
 #[cfg(feature = "Matrix3")]
-pub use mat3::Mat3;
+pub use mat3::{Mat3, Transform2D};
 
 #[cfg(feature = "Vector4")]
 mod vec4;
@@ -71,17 +92,120 @@ pub use vec4::Vec4;
 
 #[cfg(feature = "Matrix4")]
 mod mat4;
+#[cfg(all(feature = "Matrix4", feature = "Vector3"))]
+pub use mat4::ViewMatrix;
 #[cfg(feature = "Matrix4")]
-pub use mat4::{Mat4, ProjectionMatrix};
+pub use mat4::{AffineMatrix, Mat4, ProjectionMatrix};
 
 #[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
 pub use vector::MulVectorMatrix;
 
+#[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
+mod orthonormal;
+#[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
+pub use orthonormal::OrthonormalMatrix;
+
+#[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
+mod lu;
+#[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
+pub use lu::{Lu, LuDecomposition};
+
+#[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
+mod lie;
+#[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
+pub use lie::MatrixExp;
+
+#[cfg(feature = "Matrix3")]
+mod linalg;
+#[cfg(feature = "Matrix3")]
+pub use linalg::{Decomposition, Eigen, Svd};
+
 #[cfg(feature = "SliceOps")]
 pub mod slice_ops;
 
+#[cfg(feature = "Quaternion")]
+mod quat;
+#[cfg(feature = "Quaternion")]
+pub use quat::{Quat, Quaternion};
+
+#[cfg(feature = "Quaternion")]
+pub mod animation;
+
+#[cfg(feature = "Quaternion")]
+mod dual_quat;
+#[cfg(feature = "Quaternion")]
+pub use dual_quat::{DualQuat, DualQuaternion};
+
+#[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+mod trs;
+#[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+pub use trs::Trs;
+
+#[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+pub mod hierarchy;
+
+#[cfg(all(feature = "Matrix4", feature = "Vector3"))]
+pub mod camera;
+
+pub mod buffer;
+
+pub mod spline;
+
+pub mod easing;
+
+pub mod sampling;
+
+pub mod color;
+
+#[cfg(feature = "rand")]
+pub mod random;
+
+pub mod format;
+
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+
+#[cfg(any(
+    feature = "mint",
+    feature = "glam",
+    feature = "nalgebra",
+    feature = "cgmath"
+))]
+mod interop;
+#[cfg(any(
+    feature = "mint",
+    feature = "glam",
+    feature = "nalgebra",
+    feature = "cgmath"
+))]
+pub use interop::Convert;
+
+#[cfg(feature = "Operators")]
+mod operators;
+#[cfg(all(feature = "Operators", feature = "Matrix3"))]
+pub use operators::M3;
+#[cfg(all(feature = "Operators", feature = "Matrix4"))]
+pub use operators::M4;
+#[cfg(all(feature = "Operators", feature = "Vector3"))]
+pub use operators::V3;
+#[cfg(all(feature = "Operators", feature = "Vector4"))]
+pub use operators::V4;
+
+#[cfg(feature = "IntVector")]
+mod int_vector;
+#[cfg(feature = "IntVector")]
+pub use int_vector::{
+    BVec2, BVec3, BVec4, BoolVector, CompareVector, IVec2, IVec3, IVec4, IntVector, Rounding,
+    Select, ToIntVector, UVec2, UVec3, UVec4,
+};
+
 pub mod utils;
-pub use crate::matrix::Matrix;
+pub use crate::error::MathError;
+pub use crate::matrix::{Matrix, RotationMatrix};
+pub use crate::scalar::Scalar;
 pub use crate::vector::Vector;
 
 pub mod prelude;
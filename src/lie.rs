@@ -0,0 +1,353 @@
+use crate::matrix::Matrix;
+#[cfg(feature = "Matrix4")]
+use crate::orthonormal::cross;
+use crate::orthonormal::dot;
+use crate::scalar::Scalar;
+
+#[cfg(feature = "Matrix3")]
+use crate::mat3::Mat3;
+#[cfg(feature = "Matrix4")]
+use crate::mat4::{AffineMatrix, Mat4};
+
+/// The exponential and logarithm maps of rotation (SO(3)) and rigid (SE(3)) matrices
+///
+/// The tangent of a rotation `Mat3` is its rotation vector, i.e. the rotation axis scaled
+/// by the angle. The tangent of a rigid `Mat4` is `[ω, v]`, where `ω` is the rotation vector
+/// and `v` is the linear velocity.
+pub trait MatrixExp: Matrix {
+    /// The tangent vector type
+    type Tangent;
+
+    /// Compute the transformation for the given tangent vector
+    fn exp(tangent: &Self::Tangent) -> Self::MatrixType;
+
+    /// Compute the tangent vector of this transformation, the inverse of `exp`
+    ///
+    /// The rotation angle of the result is in `[0, π]`.
+    fn log(&self) -> Self::Tangent;
+
+    /// Apply this transformation `t` times, e.g. `t = 0.5` results in half of the transformation
+    fn pow(&self, t: Self::ScalarType) -> Self::MatrixType;
+
+    /// Interpolate between this transformation (`t = 0`) and `other` (`t = 1`) with constant
+    /// angular and linear velocity
+    fn interpolate(&self, other: &Self::MatrixType, t: Self::ScalarType) -> Self::MatrixType;
+}
+
+// `A = sin(θ) / θ`, `B = (1 - cos(θ)) / θ^2` and `C = (θ - sin(θ)) / θ^3` of the Rodrigues formula
+fn rodrigues_coefficients<T: Scalar>(theta: T) -> (T, T, T) {
+    let theta2 = theta * theta;
+    if theta <= T::EPSILON.sqrt() {
+        let c = |x: f32| T::from_f32(x);
+        (
+            T::ONE - theta2 * c(1. / 6.),
+            c(0.5) - theta2 * c(1. / 24.),
+            c(1. / 6.) - theta2 * c(1. / 120.),
+        )
+    } else {
+        let (s, c) = theta.sin_cos();
+        (
+            s / theta,
+            (T::ONE - c) / theta2,
+            (theta - s) / (theta2 * theta),
+        )
+    }
+}
+
+// `exp([ω]×)` as column-major 3x3 matrix
+fn so3_exp<T: Scalar>(w: &[T; 3]) -> [T; 9] {
+    let theta = dot(w, w).sqrt();
+    let (a, b, _) = rodrigues_coefficients(theta);
+
+    let [x, y, z] = *w;
+    let d = T::ONE - b * theta * theta;
+    [
+        d + b * x * x,
+        b * y * x + a * z,
+        b * z * x - a * y,
+        b * x * y - a * z,
+        d + b * y * y,
+        b * z * y + a * x,
+        b * x * z + a * y,
+        b * y * z - a * x,
+        d + b * z * z,
+    ]
+}
+
+// The rotation vector of the given column-major rotation matrix
+fn so3_log<T: Scalar>(r: &[T; 9]) -> [T; 3] {
+    let half = T::from_f32(0.5);
+    let cos = (r[0] + r[4] + r[8] - T::ONE) * half;
+    // `2 sin(θ) * axis`
+    let vee = [r[5] - r[7], r[6] - r[2], r[1] - r[3]];
+    // unlike `acos`, this stays accurate close to 0 and π
+    let theta = (dot(&vee, &vee).sqrt() * half).atan2(cos);
+
+    if cos < T::ZERO {
+        // close to π the antisymmetric part vanishes, recover the axis from the symmetric part
+        let i = if r[0] >= r[4] && r[0] >= r[8] {
+            0
+        } else if r[4] >= r[8] {
+            1
+        } else {
+            2
+        };
+        let one_minus_cos = T::ONE - cos;
+        let ni = ((r[4 * i] - cos) / one_minus_cos).sqrt();
+
+        let mut n = [T::ZERO; 3];
+        for (j, nj) in n.iter_mut().enumerate() {
+            *nj = if j == i {
+                ni
+            } else {
+                (r[3 * i + j] + r[3 * j + i]) * half / (ni * one_minus_cos)
+            };
+        }
+        if dot(&n, &vee) < T::ZERO {
+            n = [-n[0], -n[1], -n[2]];
+        }
+        [n[0] * theta, n[1] * theta, n[2] * theta]
+    } else {
+        let (a, _, _) = rodrigues_coefficients(theta);
+        let f = half / a;
+        [vee[0] * f, vee[1] * f, vee[2] * f]
+    }
+}
+
+#[cfg(feature = "Matrix3")]
+impl<T: Scalar> MatrixExp for Mat3<T> {
+    type Tangent = [T; 3];
+
+    fn exp(tangent: &[T; 3]) -> Self {
+        so3_exp(tangent)
+    }
+
+    fn log(&self) -> [T; 3] {
+        so3_log(self)
+    }
+
+    fn pow(&self, t: T) -> Self {
+        let w = self.log();
+        so3_exp(&[w[0] * t, w[1] * t, w[2] * t])
+    }
+
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        // `self * (self^T * other)^t`
+        let mut delta = *other;
+        let mut inv = *self;
+        inv.transpose();
+        delta.mul(&inv);
+
+        let mut out = delta.pow(t);
+        out.mul(self);
+        out
+    }
+}
+
+#[cfg(feature = "Matrix4")]
+fn rotation_part<T: Scalar>(m: &Mat4<T>) -> [T; 9] {
+    [m[0], m[1], m[2], m[4], m[5], m[6], m[8], m[9], m[10]]
+}
+
+#[cfg(feature = "Matrix4")]
+impl<T: Scalar> MatrixExp for Mat4<T> {
+    type Tangent = [T; 6];
+
+    fn exp(tangent: &[T; 6]) -> Self {
+        let w = [tangent[0], tangent[1], tangent[2]];
+        let v = [tangent[3], tangent[4], tangent[5]];
+
+        let theta = dot(&w, &w).sqrt();
+        let (_, b, c) = rodrigues_coefficients(theta);
+        let r = so3_exp(&w);
+
+        // `V * v` with `V = I + B [ω]× + C [ω]×^2`
+        let wv = cross(&w, &v);
+        let wwv = cross(&w, &wv);
+        let t = [
+            v[0] + b * wv[0] + c * wwv[0],
+            v[1] + b * wv[1] + c * wwv[1],
+            v[2] + b * wv[2] + c * wwv[2],
+        ];
+
+        let o = T::ZERO;
+        [
+            r[0],
+            r[1],
+            r[2],
+            o,
+            r[3],
+            r[4],
+            r[5],
+            o,
+            r[6],
+            r[7],
+            r[8],
+            o,
+            t[0],
+            t[1],
+            t[2],
+            T::ONE,
+        ]
+    }
+
+    fn log(&self) -> [T; 6] {
+        let w = so3_log(&rotation_part(self));
+        let t = [self[12], self[13], self[14]];
+
+        let theta = dot(&w, &w).sqrt();
+        let (a, b, _) = rodrigues_coefficients(theta);
+        let half = T::from_f32(0.5);
+        // `V^-1 = I - [ω]× / 2 + D [ω]×^2`
+        let d = if theta <= T::EPSILON.sqrt() {
+            T::from_f32(1. / 12.) + theta * theta * T::from_f32(1. / 720.)
+        } else {
+            (T::ONE - a * half / b) / (theta * theta)
+        };
+
+        let wt = cross(&w, &t);
+        let wwt = cross(&w, &wt);
+        [
+            w[0],
+            w[1],
+            w[2],
+            t[0] - half * wt[0] + d * wwt[0],
+            t[1] - half * wt[1] + d * wwt[1],
+            t[2] - half * wt[2] + d * wwt[2],
+        ]
+    }
+
+    fn pow(&self, t: T) -> Self {
+        let mut tangent = self.log();
+        for x in &mut tangent {
+            *x *= t;
+        }
+        Self::exp(&tangent)
+    }
+
+    /// Interpolate the rigid part using `exp` and `log`. A uniform scale is interpolated
+    /// linearly and applied after the rotation, so no shear is introduced.
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        let rigid = |m: &Self| {
+            let len = |i: usize| (m[i] * m[i] + m[i + 1] * m[i + 1] + m[i + 2] * m[i + 2]).sqrt();
+            let scale = (len(0) + len(4) + len(8)) / T::from_f32(3.);
+
+            let mut g = *m;
+            for x in g[..11].iter_mut() {
+                *x /= scale;
+            }
+            (g, scale)
+        };
+        let (a, scale_a) = rigid(self);
+        let (b, scale_b) = rigid(other);
+
+        // `a * (a^-1 * b)^t`
+        let mut inv = a;
+        inv.inverse_rigid();
+        let mut delta = b;
+        delta.mul(&inv);
+
+        let mut out = delta.pow(t);
+        out.mul(&a);
+
+        let scale = scale_a + (scale_b - scale_a) * t;
+        for x in out[..11].iter_mut() {
+            *x *= scale;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::RotationMatrix;
+    use crate::utils::almost_eq;
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn lie_mat3_round_trip() {
+        let axis = [1., -2., 0.5];
+        let len = (1. + 4. + 0.25f32).sqrt();
+
+        for &angle in &[0., 1e-4, 0.3, 2.5, std::f32::consts::PI - 1e-3] {
+            let mut r = Mat3::identity();
+            r.rotate_axis(angle, &axis);
+
+            let w = r.log();
+            let expected = [
+                axis[0] * angle / len,
+                axis[1] * angle / len,
+                axis[2] * angle / len,
+            ];
+            assert!(almost_eq(&w, &expected), "{}: {:?}", angle, w);
+            assert!(almost_eq(&Mat3::exp(&w), &r));
+        }
+    }
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn lie_mat3_pow_interpolate() {
+        let mut r = Mat3::identity();
+        r.rotate_x(1.2);
+        let mut half = Mat3::identity();
+        half.rotate_x(0.6);
+        assert!(almost_eq(&r.pow(0.5), &half));
+
+        let mut a = Mat3::identity();
+        a.rotate_z(0.4);
+        let mut b = Mat3::identity();
+        b.rotate_z(1.4);
+        let mut mid = Mat3::identity();
+        mid.rotate_z(0.9);
+        assert!(almost_eq(&a.interpolate(&b, 0.5), &mid));
+        assert!(almost_eq(&a.interpolate(&b, 0.), &a));
+        assert!(almost_eq(&a.interpolate(&b, 1.), &b));
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn lie_mat4_round_trip() {
+        let mut m: Mat4 = Mat4::identity();
+        m.translate(&[3., -5., 7.]).rotate(0.8, &[0., 1., 1.]);
+
+        let tangent = m.log();
+        assert!(almost_eq(&Mat4::exp(&tangent), &m));
+
+        let mut t: Mat4 = Mat4::identity();
+        t.translate(&[1., 2., 3.]);
+        assert!(almost_eq(&t.log(), &[0., 0., 0., 1., 2., 3.]));
+
+        let mut half = Mat4::identity();
+        half.translate(&[0.5, 1., 1.5]);
+        assert!(almost_eq(&t.pow(0.5), &half));
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn lie_mat4_interpolate() {
+        let mut a: Mat4 = Mat4::identity();
+        a.translate(&[1., 0., 0.]).rotate_y(0.2);
+        let mut b: Mat4 = Mat4::identity();
+        b.translate(&[1., 0., 4.]).rotate_y(1.2);
+
+        assert!(almost_eq(&a.interpolate(&b, 0.), &a));
+        assert!(almost_eq(&a.interpolate(&b, 1.), &b));
+
+        // the interpolated rotation is exact, unlike lerping the elements
+        let mid = a.interpolate(&b, 0.5);
+        let mut rotation = Mat4::identity();
+        rotation.rotate_y(0.7);
+        assert!(almost_eq(&mid[..11], &rotation[..11]));
+
+        // uniform scale does not introduce shear
+        let mut scaled = b;
+        for x in scaled[..11].iter_mut() {
+            *x *= 3.;
+        }
+        let mid = a.interpolate(&scaled, 0.5);
+        let mut expected = rotation;
+        expected.scale(2.);
+        assert!(almost_eq(&mid[..11], &expected[..11]));
+    }
+}
@@ -0,0 +1,366 @@
+use crate::mat3::Mat3;
+use crate::matrix::Matrix;
+use crate::orthonormal::{columns, cross, dot, set_columns, Columns};
+use crate::scalar::Scalar;
+use crate::vec3::Vec3;
+
+/// The maximum number of sweeps of the Jacobi eigenvalue algorithm
+const MAX_JACOBI_SWEEPS: usize = 50;
+
+/// The eigen-decomposition `A = V * diag(values) * V^T` of a symmetric matrix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Eigen<T = f32> {
+    /// The eigenvalues in descending order
+    pub values: Vec3<T>,
+    /// The eigenvectors as the columns of a rotation matrix, in the order of `values`
+    pub vectors: Mat3<T>,
+}
+
+/// The singular value decomposition `A = U * diag(values) * V^T`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Svd<T = f32> {
+    /// The left singular vectors as columns
+    pub u: Mat3<T>,
+    /// The non-negative singular values in descending order
+    pub values: Vec3<T>,
+    /// The right singular vectors as columns
+    pub v: Mat3<T>,
+}
+
+/// Numerical decompositions of a 3x3 matrix
+///
+/// The matrix is interpreted column-major, i.e. `A * x` is `mul_vector_left`.
+pub trait Decomposition: Matrix {
+    /// Compute the eigenvalues and eigenvectors of this symmetric matrix using the Jacobi
+    /// eigenvalue algorithm
+    ///
+    /// Only the lower triangle is read, the matrix is assumed to be symmetric.
+    fn eigen_symmetric(&self) -> Eigen<Self::ScalarType>;
+
+    /// Compute the singular value decomposition of this matrix
+    fn svd(&self) -> Svd<Self::ScalarType>;
+}
+
+fn to_mat3<T: Scalar>(c: &Columns<T>) -> Mat3<T> {
+    let mut m = [T::ZERO; 9];
+    set_columns(&mut m, 3, c);
+    m
+}
+
+// cyclic Jacobi rotations `A = J^T A J` until all off-diagonal elements vanish,
+// the columns of the returned matrix are the accumulated rotations
+fn jacobi<T: Scalar>(a: &mut Columns<T>) -> Columns<T> {
+    let (o, l) = (T::ZERO, T::ONE);
+    let mut v = [[l, o, o], [o, l, o], [o, o, l]];
+    let hundred = T::from_f32(100.);
+
+    for _ in 0..MAX_JACOBI_SWEEPS {
+        if a[0][1] == o && a[0][2] == o && a[1][2] == o {
+            break;
+        }
+
+        for &(p, q) in &[(0, 1), (0, 2), (1, 2)] {
+            let apq = a[p][q];
+            if apq == o {
+                continue;
+            }
+
+            // negligible compared to the diagonal
+            let g = hundred * apq.abs();
+            if a[p][p].abs() + g == a[p][p].abs() && a[q][q].abs() + g == a[q][q].abs() {
+                a[p][q] = o;
+                a[q][p] = o;
+                continue;
+            }
+
+            let theta = (a[q][q] - a[p][p]) / (apq + apq);
+            let t = l / (theta.abs() + (theta * theta + l).sqrt());
+            let t = if theta < o { -t } else { t };
+            let c = l / (t * t + l).sqrt();
+            let s = t * c;
+
+            for row in a.iter_mut().chain(v.iter_mut()) {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (rp, rq) = (a[p], a[q]);
+            for k in 0..3 {
+                a[p][k] = c * rp[k] - s * rq[k];
+                a[q][k] = s * rp[k] + c * rq[k];
+            }
+            a[p][q] = o;
+            a[q][p] = o;
+        }
+    }
+
+    // `v` is indexed by rows, return its columns
+    [
+        [v[0][0], v[1][0], v[2][0]],
+        [v[0][1], v[1][1], v[2][1]],
+        [v[0][2], v[1][2], v[2][2]],
+    ]
+}
+
+fn eigen<T: Scalar>(m: &Columns<T>) -> (Vec3<T>, Columns<T>) {
+    let mut a = *m;
+    // mirror the lower triangle
+    a[0][1] = a[1][0];
+    a[0][2] = a[2][0];
+    a[1][2] = a[2][1];
+
+    let vectors = jacobi(&mut a);
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| {
+        a[j][j]
+            .partial_cmp(&a[i][i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let values = [
+        a[order[0]][order[0]],
+        a[order[1]][order[1]],
+        a[order[2]][order[2]],
+    ];
+    let mut vectors = [vectors[order[0]], vectors[order[1]], vectors[order[2]]];
+
+    // keep the eigenvectors right-handed
+    if dot(&vectors[0], &cross(&vectors[1], &vectors[2])) < T::ZERO {
+        for x in &mut vectors[2] {
+            *x = -*x;
+        }
+    }
+
+    (values, vectors)
+}
+
+// `None` if the length of `v` is at most `tolerance`
+fn normalized<T: Scalar>(v: &[T; 3], tolerance: T) -> Option<[T; 3]> {
+    let len = dot(v, v).sqrt();
+    if len <= tolerance {
+        None
+    } else {
+        Some([v[0] / len, v[1] / len, v[2] / len])
+    }
+}
+
+// any unit vector perpendicular to the given unit vector
+fn perpendicular<T: Scalar>(v: &[T; 3]) -> [T; 3] {
+    let (o, l) = (T::ZERO, T::ONE);
+    let axis = if v[0].abs() < T::from_f32(0.9) {
+        [l, o, o]
+    } else {
+        [o, l, o]
+    };
+    let p = cross(v, &axis);
+    let len = dot(&p, &p).sqrt();
+    [p[0] / len, p[1] / len, p[2] / len]
+}
+
+impl<T: Scalar> Decomposition for Mat3<T> {
+    fn eigen_symmetric(&self) -> Eigen<T> {
+        let (values, vectors) = eigen(&columns(self, 3));
+        Eigen {
+            values,
+            vectors: to_mat3(&vectors),
+        }
+    }
+
+    fn svd(&self) -> Svd<T> {
+        let (o, l) = (T::ZERO, T::ONE);
+        let a = columns(self, 3);
+
+        // the right singular vectors are the eigenvectors of `A^T A`
+        let mut ata = [[o; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                ata[i][j] = dot(&a[i], &a[j]);
+            }
+        }
+        let (_, v) = eigen(&ata);
+
+        // `A V = U S`, orthonormalize the columns of `A V` to get `U`
+        let av = |i: usize| {
+            let mut out = [o; 3];
+            for (col, &x) in a.iter().zip(v[i].iter()) {
+                for k in 0..3 {
+                    out[k] += col[k] * x;
+                }
+            }
+            out
+        };
+        let b = [av(0), av(1), av(2)];
+
+        // relative to the largest singular value, so small matrices keep their `U`
+        let tolerance = T::EPSILON * dot(&b[0], &b[0]).sqrt();
+        let u0 = normalized(&b[0], tolerance).unwrap_or([l, o, o]);
+        let d = dot(&u0, &b[1]);
+        let u1 = normalized(
+            &[
+                b[1][0] - d * u0[0],
+                b[1][1] - d * u0[1],
+                b[1][2] - d * u0[2],
+            ],
+            tolerance,
+        )
+        .unwrap_or_else(|| perpendicular(&u0));
+        let mut u2 = cross(&u0, &u1);
+
+        let mut values = [dot(&u0, &b[0]), dot(&u1, &b[1]), dot(&u2, &b[2])];
+        if values[2] < o {
+            values[2] = -values[2];
+            for x in &mut u2 {
+                *x = -*x;
+            }
+        }
+
+        Svd {
+            u: to_mat3(&[u0, u1, u2]),
+            values,
+            v: to_mat3(&v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::RotationMatrix;
+    use crate::orthonormal::OrthonormalMatrix;
+    use crate::utils::{almost_eq, almost_eq_relative};
+
+    fn reconstruct(u: &Mat3, values: &Vec3, v: &Mat3) -> Mat3 {
+        let mut s: Mat3 = Mat3::zeros();
+        s[0] = values[0];
+        s[4] = values[1];
+        s[8] = values[2];
+
+        let mut vt = *v;
+        vt.transpose();
+
+        // `a.mul(b)` computes `b * a` in column-major notation
+        let mut out = vt;
+        out.mul(&s).mul(u);
+        out
+    }
+
+    #[test]
+    fn linalg_eigen_symmetric() {
+        let a: Mat3 = [4., 1., -2., 1., 2., 0., -2., 0., 3.];
+        let e = a.eigen_symmetric();
+
+        assert!(e.values[0] >= e.values[1] && e.values[1] >= e.values[2]);
+        assert!(e.vectors.is_rotation());
+        assert!(almost_eq_relative(
+            &reconstruct(&e.vectors, &e.values, &e.vectors),
+            &a,
+            1e-5,
+            1e-5
+        ));
+
+        for i in 0..3 {
+            let v = &e.vectors[3 * i..3 * i + 3];
+            let av = a.mul_vector_left(v);
+            let lv = [v[0] * e.values[i], v[1] * e.values[i], v[2] * e.values[i]];
+            assert!(almost_eq_relative(&av, &lv, 1e-5, 1e-5));
+        }
+    }
+
+    #[test]
+    fn linalg_eigen_diagonal() {
+        let a: Mat3 = [1., 0., 0., 0., 3., 0., 0., 0., 2.];
+        let e = a.eigen_symmetric();
+        assert_eq!(e.values, [3., 2., 1.]);
+        assert!(e.vectors.is_rotation());
+    }
+
+    #[test]
+    fn linalg_svd() {
+        let a: Mat3 = [1., 3., 2., 4., 2., 8., 9., 2., 7.];
+        let svd = a.svd();
+
+        assert!(svd.values[0] >= svd.values[1] && svd.values[1] >= svd.values[2]);
+        assert!(svd.values[2] >= 0.);
+        assert!(svd.u.is_orthonormal());
+        assert!(svd.v.is_orthonormal());
+        assert!(almost_eq_relative(
+            &reconstruct(&svd.u, &svd.values, &svd.v),
+            &a,
+            1e-4,
+            1e-4
+        ));
+    }
+
+    #[test]
+    fn linalg_svd_small_scale() {
+        // e.g. the covariance of a flat point cloud in millimetres, the two smaller singular
+        // values are below `Scalar::EPSILON`
+        let r1: Mat3 = Mat3::from_axis_angle(&[1., 2., 3.], 0.7);
+        let r2: Mat3 = Mat3::from_axis_angle(&[-2., 1., 0.5], 1.3);
+        let mut a = reconstruct(&r1, &[4., 0.05, 0.02], &r2);
+        a.scale(1e-4);
+        let svd = a.svd();
+
+        assert!(svd.u.is_rotation());
+        assert!(almost_eq_relative(
+            &svd.values,
+            &[4e-4, 5e-6, 2e-6],
+            1e-10,
+            1e-3
+        ));
+        assert!(almost_eq_relative(
+            &reconstruct(&svd.u, &svd.values, &svd.v),
+            &a,
+            1e-10,
+            1e-3
+        ));
+    }
+
+    #[test]
+    fn linalg_svd_rank_deficient() {
+        let a: Mat3 = [1., 2., 3., 2., 4., 6., 0., 0., 0.];
+        let svd = a.svd();
+
+        assert!(svd.values[1].abs() < 1e-3 && svd.values[2].abs() < 1e-3);
+        assert!(svd.u.is_orthonormal());
+        assert!(almost_eq_relative(
+            &reconstruct(&svd.u, &svd.values, &svd.v),
+            &a,
+            1e-4,
+            1e-4
+        ));
+
+        let zero: Mat3 = Mat3::zeros();
+        let svd = zero.svd();
+        assert!(almost_eq(&svd.values, &[0., 0., 0.]));
+        assert!(svd.u.is_rotation());
+    }
+
+    #[test]
+    fn linalg_svd_kabsch() {
+        // recover a rotation from point correspondences
+        let mut r: Mat3 = Mat3::identity();
+        r.rotate_axis(0.9, &[1., -2., 0.5]);
+
+        let points = [[1., 0., 2.], [-1., 3., 0.5], [0., -2., 1.], [2., 2., -1.]];
+        let mut h: Mat3 = Mat3::zeros();
+        for p in &points {
+            let q = r.mul_vector_left(p);
+            // `H += p * q^T`
+            for col in 0..3 {
+                for row in 0..3 {
+                    h[3 * col + row] += p[row] * q[col];
+                }
+            }
+        }
+
+        let svd = h.svd();
+        let mut ut = svd.u;
+        ut.transpose();
+        // `R = V * U^T`
+        let mut found = ut;
+        found.mul(&svd.v);
+        assert!(almost_eq(&found, &r));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::error::{check_len, MathError};
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use std::cmp::Ordering;
+
+#[cfg(feature = "Matrix3")]
+use crate::mat3::{Mat3, Vec3};
+#[cfg(feature = "Matrix4")]
+use crate::mat4::Mat4;
+#[cfg(feature = "Matrix4")]
+use crate::vec4::Vec4;
+
+/// The LU decomposition `P * A = L * U` with partial pivoting, see `LuDecomposition`
+///
+/// The matrix is interpreted column-major, i.e. the solutions `x` of `A * x = b`
+/// satisfy `A.mul_vector_left(&x) == b`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lu<M> {
+    // `U` on and above the diagonal, `L` below it with an implicit unit diagonal
+    factors: M,
+    // row `i` of `P * A` is row `pivots[i]` of `A`
+    pivots: [usize; 4],
+    odd_permutation: bool,
+}
+
+/// Factorization and linear system solving
+pub trait LuDecomposition: Matrix {
+    /// Compute the LU decomposition of this matrix
+    ///
+    /// Fails with `MathError::Singular` if a pivot is at most `Scalar::EPSILON` times the
+    /// largest absolute element.
+    fn lu(&self) -> Result<Lu<Self::MatrixType>, MathError> {
+        self.lu_with_tolerance(Self::ScalarType::EPSILON)
+    }
+
+    /// Same as `lu`, but with a custom relative tolerance for the pivots
+    fn lu_with_tolerance(
+        &self,
+        tolerance: Self::ScalarType,
+    ) -> Result<Lu<Self::MatrixType>, MathError>;
+
+    /// Solve `A * x = rhs` for `x`
+    fn solve(&self, rhs: &[Self::ScalarType]) -> Result<Self::VectorType, MathError>;
+
+    /// Compute the condition number in the 1-norm. Returns `None` if the matrix is singular.
+    ///
+    /// Large values mean that solutions are sensitive to errors in the matrix or `rhs`.
+    fn condition_number(&self) -> Option<Self::ScalarType>;
+}
+
+// Factors the `n` x `n` column-major matrix `m` in place
+fn factor<T: Scalar>(m: &mut [T], n: usize, tolerance: T) -> Result<([usize; 4], bool), MathError> {
+    let mut pivots = [0, 1, 2, 3];
+    let mut odd = false;
+
+    let scale = m
+        .iter()
+        .fold(T::ZERO, |acc, &x| if x.abs() > acc { x.abs() } else { acc });
+    let threshold = tolerance * scale;
+
+    for k in 0..n {
+        let mut p = k;
+        for r in k + 1..n {
+            if m[k * n + r].abs() > m[k * n + p].abs() {
+                p = r;
+            }
+        }
+
+        // also catches NaNs
+        if m[k * n + p].abs().partial_cmp(&threshold) != Some(Ordering::Greater) {
+            return Err(MathError::Singular);
+        }
+
+        if p != k {
+            for c in 0..n {
+                m.swap(c * n + k, c * n + p);
+            }
+            pivots.swap(k, p);
+            odd = !odd;
+        }
+
+        let pivot = m[k * n + k];
+        for r in k + 1..n {
+            let factor = m[k * n + r] / pivot;
+            m[k * n + r] = factor;
+            for c in k + 1..n {
+                let u = m[c * n + k];
+                m[c * n + r] -= factor * u;
+            }
+        }
+    }
+
+    Ok((pivots, odd))
+}
+
+// Solves `L * U * x = P * b` in place
+fn substitute<T: Scalar>(factors: &[T], n: usize, pivots: &[usize; 4], b: &mut [T]) {
+    let mut y = [T::ZERO; 4];
+    for (yi, &p) in y.iter_mut().zip(pivots.iter()).take(n) {
+        *yi = b[p];
+    }
+
+    for r in 0..n {
+        for c in 0..r {
+            y[r] -= factors[c * n + r] * y[c];
+        }
+    }
+    for r in (0..n).rev() {
+        for c in r + 1..n {
+            y[r] -= factors[c * n + r] * y[c];
+        }
+        y[r] /= factors[r * n + r];
+    }
+
+    b[..n].copy_from_slice(&y[..n]);
+}
+
+// The maximum absolute column sum
+fn norm1<T: Scalar>(m: &[T], n: usize) -> T {
+    m.chunks_exact(n)
+        .map(|col| col.iter().fold(T::ZERO, |acc, &x| acc + x.abs()))
+        .fold(T::ZERO, |acc, x| if x > acc { x } else { acc })
+}
+
+macro_rules! impl_lu {
+    ($mat:ident, $vec:ident, $n:expr) => {
+        impl<T: Scalar> Lu<$mat<T>> {
+            /// Solve `A * x = rhs` for `x`
+            pub fn solve(&self, rhs: &[T]) -> Result<$vec<T>, MathError> {
+                check_len(rhs.len(), $n)?;
+
+                let mut x = [T::ZERO; $n];
+                x.copy_from_slice(rhs);
+                substitute(&self.factors, $n, &self.pivots, &mut x);
+                Ok(x)
+            }
+
+            /// Compute the determinant of the decomposed matrix
+            pub fn det(&self) -> T {
+                let mut det = if self.odd_permutation {
+                    -T::ONE
+                } else {
+                    T::ONE
+                };
+                for i in 0..$n {
+                    det *= self.factors[i * $n + i];
+                }
+                det
+            }
+
+            /// Compute the inverse of the decomposed matrix
+            pub fn inverse(&self) -> $mat<T> {
+                let mut inv = [T::ZERO; $n * $n];
+                for (i, col) in inv.chunks_exact_mut($n).enumerate() {
+                    col[i] = T::ONE;
+                    substitute(&self.factors, $n, &self.pivots, col);
+                }
+                inv
+            }
+        }
+
+        impl<T: Scalar> LuDecomposition for $mat<T> {
+            fn lu_with_tolerance(&self, tolerance: T) -> Result<Lu<Self>, MathError> {
+                let mut factors = *self;
+                let (pivots, odd_permutation) = factor(&mut factors, $n, tolerance)?;
+                Ok(Lu {
+                    factors,
+                    pivots,
+                    odd_permutation,
+                })
+            }
+
+            fn solve(&self, rhs: &[T]) -> Result<$vec<T>, MathError> {
+                check_len(rhs.len(), $n)?;
+                self.lu()?.solve(rhs)
+            }
+
+            fn condition_number(&self) -> Option<T> {
+                let inv = self.lu().ok()?.inverse();
+                Some(norm1(self, $n) * norm1(&inv, $n))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "Matrix3")]
+impl_lu!(Mat3, Vec3, 3);
+#[cfg(feature = "Matrix4")]
+impl_lu!(Mat4, Vec4, 4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq;
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn lu_mat3_solve() {
+        let a: Mat3 = [1., 3., 2., 4., 2., 8., 9., 2., 7.];
+        let b = [1., -2., 3.];
+
+        let x = a.solve(&b).expect("Not singular");
+        assert!(almost_eq(&a.mul_vector_left(&x), &b));
+
+        let lu = a.lu().expect("Not singular");
+        assert!(almost_eq(&[lu.det()], &[a.det()]));
+
+        let mut inv = a;
+        inv.inverse();
+        assert!(almost_eq(&lu.inverse(), &inv));
+    }
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn lu_mat3_singular() {
+        let a: Mat3 = [9., 8., 7., 6., 5., 4., 3., 2., 1.];
+        assert_eq!(a.lu(), Err(MathError::Singular));
+        assert_eq!(a.solve(&[1., 2., 3.]), Err(MathError::Singular));
+        assert_eq!(a.condition_number(), None);
+
+        // nearly singular matrices are caught by the tolerance
+        let b: Mat3 = [1., 2., 3., 2., 4., 6.000001, 0., 1., 0.];
+        assert!(b.lu_with_tolerance(0.).is_ok());
+        assert_eq!(b.lu(), Err(MathError::Singular));
+
+        let zeros: Mat3 = Mat3::zeros();
+        assert_eq!(zeros.lu(), Err(MathError::Singular));
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn lu_mat4_solve() {
+        // requires pivoting, the first element is zero
+        let a: Mat4 = [
+            0., 3., 2., 4., 2., 8., 9., 2., 7., 5., 1., 3., 6., 2., 4., 1.,
+        ];
+        let b = [1., -2., 3., 0.5];
+
+        let x = a.solve(&b).expect("Not singular");
+        assert!(almost_eq(&a.mul_vector_left(&x), &b));
+
+        let lu = a.lu().expect("Not singular");
+        assert!((lu.det() - a.det()).abs() <= 1e-3);
+
+        assert_eq!(
+            a.solve(&b[..3]),
+            Err(MathError::DimensionMismatch {
+                expected: 4,
+                found: 3
+            })
+        );
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn lu_mat4_condition_number() {
+        let id: Mat4 = Mat4::identity();
+        assert_eq!(id.condition_number(), Some(1.));
+
+        let mut scaled = id;
+        scaled[0] = 1e-3;
+        let cond = scaled.condition_number().expect("Not singular");
+        assert!((cond - 1e3).abs() <= 1e-2);
+    }
+}
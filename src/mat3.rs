@@ -1,22 +1,29 @@
-use crate::matrix::Matrix;
-use crate::utils::EPSILON;
-use std::f32;
+use crate::error::MathError;
+use crate::matrix::{Matrix, RotationMatrix};
+use crate::scalar::Scalar;
 
-pub type Mat3 = [f32; 9];
-pub type Vec3 = [f32; 3];
+#[cfg(feature = "Quaternion")]
+use crate::quat::{Quat, Quaternion};
 
-impl Matrix for Mat3 {
-    type MatrixType = Mat3;
-    type VectorType = Vec3;
+pub type Mat3<T = f32> = [T; 9];
+pub type Vec3<T = f32> = [T; 3];
+
+impl<T: Scalar> Matrix for Mat3<T> {
+    type MatrixType = Mat3<T>;
+    type VectorType = Vec3<T>;
+    type ScalarType = T;
+
+    const DIMENSION: usize = 3;
 
     fn zeros() -> Self {
-        [0., 0., 0., 0., 0., 0., 0., 0., 0.]
+        [T::ZERO; 9]
     }
     fn ones() -> Self {
-        [1., 1., 1., 1., 1., 1., 1., 1., 1.]
+        [T::ONE; 9]
     }
     fn identity() -> Self {
-        [1., 0., 0., 0., 1., 0., 0., 0., 1.]
+        let (o, l) = (T::ZERO, T::ONE);
+        [l, o, o, o, l, o, o, o, l]
     }
 
     fn copy_to(&self, dst: &mut Self) {
@@ -79,12 +86,12 @@ impl Matrix for Mat3 {
         self
     }
 
-    fn mul_vector(&self, rhs: &[f32]) -> Vec3 {
+    fn mul_vector(&self, rhs: &[T]) -> Vec3<T> {
         debug_assert!(rhs.len() > 1);
 
         let x = rhs[0];
         let y = rhs[1];
-        let w = if rhs.len() > 2 { rhs[2] } else { 1. };
+        let w = if rhs.len() > 2 { rhs[2] } else { T::ONE };
         [
             self[0] * x + self[1] * y + self[2] * w,
             self[3] * x + self[4] * y + self[5] * w,
@@ -92,11 +99,11 @@ impl Matrix for Mat3 {
         ]
     }
 
-    fn mul_vector_left(&self, lhs: &[f32]) -> Vec3 {
+    fn mul_vector_left(&self, lhs: &[T]) -> Vec3<T> {
         debug_assert!(lhs.len() > 1);
         let x = lhs[0];
         let y = lhs[1];
-        let w = if lhs.len() > 2 { lhs[2] } else { 1. };
+        let w = if lhs.len() > 2 { lhs[2] } else { T::ONE };
         [
             self[0] * x + self[3] * y + self[6] * w,
             self[1] * x + self[4] * y + self[7] * w,
@@ -132,7 +139,7 @@ impl Matrix for Mat3 {
         self
     }
 
-    fn scale(&mut self, factor: f32) -> &mut Self {
+    fn scale(&mut self, factor: T) -> &mut Self {
         self[0] *= factor;
         self[1] *= factor;
         self[2] *= factor;
@@ -163,11 +170,11 @@ impl Matrix for Mat3 {
 
         let det = v00 * tmp01 + v01 * tmp11 + v02 * tmp21;
 
-        if det.abs() <= EPSILON {
+        if det.abs() <= T::EPSILON {
             return None;
         }
 
-        let det_inv = 1.0 / det;
+        let det_inv = T::ONE / det;
 
         self[0] = tmp01 * det_inv;
         self[1] = (-v22 * v01 + v02 * v21) * det_inv;
@@ -182,7 +189,7 @@ impl Matrix for Mat3 {
         Some(self)
     }
 
-    fn det(&self) -> f32 {
+    fn det(&self) -> T {
         let v00 = self[0];
         let v01 = self[1];
         let v02 = self[2];
@@ -222,7 +229,7 @@ impl Matrix for Mat3 {
         self
     }
 
-    fn translate(&mut self, direction: &[f32]) -> &mut Self {
+    fn translate(&mut self, direction: &[T]) -> &mut Self {
         debug_assert!(direction.len() > 1);
 
         let mut x = direction[0];
@@ -242,7 +249,10 @@ impl Matrix for Mat3 {
 
     /// Rotate the matrix around the Z-axis.
     /// The `axis` argument is ignored.
-    fn rotate(&mut self, angle: f32, _: &[f32]) -> &mut Self {
+    ///
+    /// This treats the matrix as a 2D homogenous transform, see `Transform2D::rotate_2d`.
+    /// For rotations around arbitrary 3D axes use `RotationMatrix::rotate_axis`.
+    fn rotate(&mut self, angle: T, _: &[T]) -> &mut Self {
         let v00 = self[0];
         let v01 = self[1];
         let v02 = self[2];
@@ -261,28 +271,183 @@ impl Matrix for Mat3 {
 
         self
     }
+
+    /// Same as `rotate`, which ignores the axis, so this never fails
+    fn try_rotate(&mut self, angle: T, axis: &[T]) -> Result<&mut Self, MathError> {
+        Ok(self.rotate(angle, axis))
+    }
+}
+
+/// `Mat3` as a homogenous transform of 2D points
+pub trait Transform2D: Matrix {
+    /// Translate this matrix into the given 2D direction
+    ///
+    /// The homogenous coordinate of `direction` can be omitted,
+    /// if so, it will be assumed to be equal to 1.
+    fn translate_2d(&mut self, direction: &[Self::ScalarType]) -> &mut Self::MatrixType;
+
+    /// Rotate this matrix by the given angle (radians) in the 2D plane
+    fn rotate_2d(&mut self, angle: Self::ScalarType) -> &mut Self::MatrixType;
+}
+
+impl<T: Scalar> Transform2D for Mat3<T> {
+    fn translate_2d(&mut self, direction: &[T]) -> &mut Self {
+        self.translate(direction)
+    }
+
+    fn rotate_2d(&mut self, angle: T) -> &mut Self {
+        self.rotate(angle, &[])
+    }
+}
+
+impl<T: Scalar> RotationMatrix for Mat3<T> {
+    fn from_axis_angle(axis: &[T], angle: T) -> Self {
+        debug_assert!(axis.len() >= 3);
+
+        let mut x = axis[0];
+        let mut y = axis[1];
+        let mut z = axis[2];
+
+        let len = (x * x + y * y + z * z).sqrt();
+
+        // no rotation around nothing
+        if len <= T::EPSILON {
+            debug_assert!(len > T::EPSILON);
+            return Self::identity();
+        }
+
+        x /= len;
+        y /= len;
+        z /= len;
+
+        let (s, c) = angle.sin_cos();
+        let t = T::ONE - c;
+
+        [
+            x * x * t + c,
+            y * x * t + z * s,
+            z * x * t - y * s,
+            x * y * t - z * s,
+            y * y * t + c,
+            z * y * t + x * s,
+            x * z * t + y * s,
+            y * z * t - x * s,
+            z * z * t + c,
+        ]
+    }
+
+    fn from_rotation_x(angle: T) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (o, l) = (T::ZERO, T::ONE);
+        [l, o, o, o, c, s, o, -s, c]
+    }
+
+    fn from_rotation_y(angle: T) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (o, l) = (T::ZERO, T::ONE);
+        [c, o, -s, o, l, o, s, o, c]
+    }
+
+    fn from_rotation_z(angle: T) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (o, l) = (T::ZERO, T::ONE);
+        [c, s, o, -s, c, o, o, o, l]
+    }
+
+    #[cfg(feature = "Quaternion")]
+    fn from_quat(q: &Quat<T>) -> Self {
+        q.to_mat3()
+    }
+
+    fn rotate_axis(&mut self, angle: T, axis: &[T]) -> &mut Self {
+        let rot = Self::from_axis_angle(axis, angle);
+
+        let v00 = self[0];
+        let v01 = self[1];
+        let v02 = self[2];
+        let v10 = self[3];
+        let v11 = self[4];
+        let v12 = self[5];
+        let v20 = self[6];
+        let v21 = self[7];
+        let v22 = self[8];
+
+        for (i, r) in rot.chunks_exact(3).enumerate() {
+            self[3 * i] = v00 * r[0] + v10 * r[1] + v20 * r[2];
+            self[3 * i + 1] = v01 * r[0] + v11 * r[1] + v21 * r[2];
+            self[3 * i + 2] = v02 * r[0] + v12 * r[1] + v22 * r[2];
+        }
+
+        self
+    }
+
+    fn rotate_x(&mut self, angle: T) -> &mut Self {
+        let v10 = self[3];
+        let v11 = self[4];
+        let v12 = self[5];
+        let v20 = self[6];
+        let v21 = self[7];
+        let v22 = self[8];
+
+        let (s, c) = angle.sin_cos();
+
+        self[3] = c * v10 + s * v20;
+        self[4] = c * v11 + s * v21;
+        self[5] = c * v12 + s * v22;
+        self[6] = c * v20 - s * v10;
+        self[7] = c * v21 - s * v11;
+        self[8] = c * v22 - s * v12;
+
+        self
+    }
+
+    fn rotate_y(&mut self, angle: T) -> &mut Self {
+        let v00 = self[0];
+        let v01 = self[1];
+        let v02 = self[2];
+        let v20 = self[6];
+        let v21 = self[7];
+        let v22 = self[8];
+
+        let (s, c) = angle.sin_cos();
+
+        self[0] = c * v00 - s * v20;
+        self[1] = c * v01 - s * v21;
+        self[2] = c * v02 - s * v22;
+        self[6] = s * v00 + c * v20;
+        self[7] = s * v01 + c * v21;
+        self[8] = s * v02 + c * v22;
+
+        self
+    }
+
+    fn rotate_z(&mut self, angle: T) -> &mut Self {
+        // the 2D rotation only touches the first two columns
+        self.rotate(angle, &[])
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utils::almost_eq;
+    use std::f32;
 
     #[test]
     fn mat3_zeros() {
-        let zeros = Mat3::zeros();
+        let zeros: Mat3 = Mat3::zeros();
         assert!(zeros.iter().all(|&x| x == 0.0));
     }
 
     #[test]
     fn mat3_ones() {
-        let ones = Mat3::ones();
+        let ones: Mat3 = Mat3::ones();
         assert!(ones.iter().all(|&x| x == 1.0));
     }
 
     #[test]
     fn mat3_identity() {
-        let i = Mat3::identity();
+        let i: Mat3 = Mat3::identity();
         assert_eq!(i[0], 1.0);
         assert_eq!(i[1], 0.0);
         assert_eq!(i[2], 0.0);
@@ -298,7 +463,7 @@ mod tests {
 
     #[test]
     fn mat3_copy_to() {
-        let mut a = Mat3::zeros();
+        let mut a: Mat3 = Mat3::zeros();
         let b = Mat3::ones();
 
         b.copy_to(&mut a);
@@ -371,8 +536,8 @@ mod tests {
 
     #[test]
     fn mat3_inverse_valid() {
-        let mut a = [1., 3., 2., 4., 2., 8., 9., 2., 7.];
-        let b = a.clone();
+        let mut a: Mat3 = [1., 3., 2., 4., 2., 8., 9., 2., 7.];
+        let b = a;
 
         let a = a.inverse().expect("Inverse should exist");
 
@@ -441,4 +606,82 @@ mod tests {
         let r = m.mul_vector_left(&v);
         assert!(almost_eq(&r, &[-3., -1., 1.]));
     }
+
+    #[test]
+    fn mat3_transform_2d() {
+        let mut a: Mat3 = Mat3::identity();
+        a.translate_2d(&[3., -5.]).rotate_2d(f32::consts::FRAC_PI_3);
+
+        let mut b: Mat3 = Mat3::identity();
+        b.translate(&[3., -5.])
+            .rotate(f32::consts::FRAC_PI_3, &[0.; 3]);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn mat3_rotate_axis() {
+        let v = [-1., 3., 5.];
+        let half_pi = f32::consts::FRAC_PI_2;
+
+        let mut m = Mat3::identity();
+        m.rotate_axis(half_pi, &[2., 0., 0.]);
+        assert!(almost_eq(&m.mul_vector_left(&v), &[-1., -5., 3.]));
+
+        let mut m = Mat3::identity();
+        m.rotate_axis(half_pi, &[0., 1., 0.]);
+        assert!(almost_eq(&m.mul_vector_left(&v), &[5., 3., 1.]));
+
+        let mut m = Mat3::identity();
+        m.rotate_axis(half_pi, &[0., 0., 1.]);
+        assert!(almost_eq(&m.mul_vector_left(&v), &[-3., -1., 5.]));
+    }
+
+    #[test]
+    fn mat3_rotate_xyz() {
+        let base: Mat3 = [1., 3., 2., 4., 2., 8., 9., 2., 7.];
+        let angle = 0.7;
+
+        let (mut a, mut b) = (base, base);
+        a.rotate_x(angle);
+        b.rotate_axis(angle, &[1., 0., 0.]);
+        assert!(almost_eq(&a, &b));
+
+        let (mut a, mut b) = (base, base);
+        a.rotate_y(angle);
+        b.rotate_axis(angle, &[0., 1., 0.]);
+        assert!(almost_eq(&a, &b));
+
+        let (mut a, mut b) = (base, base);
+        a.rotate_z(angle);
+        b.rotate_axis(angle, &[0., 0., 1.]);
+        assert!(almost_eq(&a, &b));
+
+        a.rotate_z(-angle);
+        assert!(almost_eq(&a, &base));
+    }
+
+    #[test]
+    fn mat3_from_rotation() {
+        let angle = -1.2;
+        let mut a = Mat3::identity();
+        assert!(almost_eq(&Mat3::from_rotation_x(angle), a.rotate_x(angle)));
+        let mut a = Mat3::identity();
+        assert!(almost_eq(&Mat3::from_rotation_y(angle), a.rotate_y(angle)));
+        let mut a = Mat3::identity();
+        assert!(almost_eq(&Mat3::from_rotation_z(angle), a.rotate_z(angle)));
+
+        let r: Mat3 = Mat3::from_axis_angle(&[1., 1., 0.], f32::consts::PI);
+        assert!(almost_eq(&r.mul_vector_left(&[1., 0., 0.]), &[0., 1., 0.]));
+    }
+
+    #[cfg(feature = "Quaternion")]
+    #[test]
+    fn mat3_from_quat() {
+        let axis = [1., -2., 0.5];
+        let q = Quat::from_axis_angle(&axis, 0.9);
+        let a = Mat3::from_quat(&q);
+        let b: Mat3 = Mat3::from_axis_angle(&axis, 0.9);
+        assert!(almost_eq(&a, &b));
+    }
 }
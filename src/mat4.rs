@@ -1,33 +1,36 @@
-use crate::matrix::Matrix;
-use crate::utils::EPSILON;
+use crate::error::{check_homogenous_len, check_nonzero, MathError};
+use crate::matrix::{Matrix, RotationMatrix};
+use crate::scalar::Scalar;
 use crate::vec4::Vec4;
-use std::f32;
+
+#[cfg(feature = "Quaternion")]
+use crate::quat::{Quat, Quaternion};
 
 #[cfg(feature = "Vector3")]
 use crate::vec3::Vec3;
 
-pub type Mat4 = [f32; 16];
+pub type Mat4<T = f32> = [T; 16];
+
+impl<T: Scalar> Matrix for Mat4<T> {
+    type MatrixType = Mat4<T>;
+    type VectorType = Vec4<T>;
+    type ScalarType = T;
 
-impl Matrix for Mat4 {
-    type MatrixType = Mat4;
-    type VectorType = Vec4;
+    const DIMENSION: usize = 4;
 
     fn zeros() -> Self {
-        [0.; 16]
+        [T::ZERO; 16]
     }
     fn ones() -> Self {
-        [1.; 16]
+        [T::ONE; 16]
     }
     fn identity() -> Self {
-        [
-            1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1., 0., 0., 0., 0., 1.,
-        ]
+        let (o, l) = (T::ZERO, T::ONE);
+        [l, o, o, o, o, l, o, o, o, o, l, o, o, o, o, l]
     }
 
     fn copy_to(&self, dst: &mut Self) {
-        for i in 0..16 {
-            dst[i] = self[i];
-        }
+        dst.copy_from_slice(self);
     }
 
     fn transpose(&mut self) -> &mut Self {
@@ -110,12 +113,12 @@ impl Matrix for Mat4 {
 
         self
     }
-    fn mul_vector(&self, rhs: &[f32]) -> Vec4 {
+    fn mul_vector(&self, rhs: &[T]) -> Vec4<T> {
         debug_assert!(rhs.len() >= 3);
         let x = rhs[0];
         let y = rhs[1];
         let z = rhs[2];
-        let w = if rhs.len() > 3 { rhs[3] } else { 1. };
+        let w = if rhs.len() > 3 { rhs[3] } else { T::ONE };
 
         [
             self[0] * x + self[1] * y + self[2] * z + self[3] * w,
@@ -124,12 +127,12 @@ impl Matrix for Mat4 {
             self[12] * x + self[13] * y + self[14] * z + self[15] * w,
         ]
     }
-    fn mul_vector_left(&self, lhs: &[f32]) -> Vec4 {
+    fn mul_vector_left(&self, lhs: &[T]) -> Vec4<T> {
         debug_assert!(lhs.len() >= 3);
         let x = lhs[0];
         let y = lhs[1];
         let z = lhs[2];
-        let w = if lhs.len() > 3 { lhs[3] } else { 1. };
+        let w = if lhs.len() > 3 { lhs[3] } else { T::ONE };
         [
             self[0] * x + self[4] * y + self[8] * z + self[12] * w,
             self[1] * x + self[5] * y + self[9] * z + self[13] * w,
@@ -138,23 +141,23 @@ impl Matrix for Mat4 {
         ]
     }
     fn add(&mut self, rhs: &Self) -> &mut Self {
-        for i in 0..16 {
-            self[i] += rhs[i];
+        for (v, &r) in self.iter_mut().zip(rhs.iter()) {
+            *v += r;
         }
 
         self
     }
     fn sub(&mut self, rhs: &Self) -> &mut Self {
-        for i in 0..16 {
-            self[i] -= rhs[i];
+        for (v, &r) in self.iter_mut().zip(rhs.iter()) {
+            *v -= r;
         }
 
         self
     }
 
-    fn scale(&mut self, factor: f32) -> &mut Self {
-        for i in 0..16 {
-            self[i] *= factor;
+    fn scale(&mut self, factor: T) -> &mut Self {
+        for v in self.iter_mut() {
+            *v *= factor;
         }
 
         self
@@ -194,10 +197,10 @@ impl Matrix for Mat4 {
         let det = tmp00 * tmp11 - tmp01 * tmp10 + tmp02 * tmp09 + tmp03 * tmp08 - tmp04 * tmp07
             + tmp05 * tmp06;
 
-        if det.abs() <= EPSILON {
+        if det.abs() <= T::EPSILON {
             return None;
         }
-        let det_inv = 1.0 / det;
+        let det_inv = T::ONE / det;
 
         self[0] = (v11 * tmp11 - v12 * tmp10 + v13 * tmp09) * det_inv;
         self[1] = (v02 * tmp10 - v01 * tmp11 - v03 * tmp09) * det_inv;
@@ -219,7 +222,7 @@ impl Matrix for Mat4 {
         Some(self)
     }
 
-    fn det(&self) -> f32 {
+    fn det(&self) -> T {
         let v00 = self[0];
         let v01 = self[1];
         let v02 = self[2];
@@ -308,7 +311,7 @@ impl Matrix for Mat4 {
         self
     }
 
-    fn translate(&mut self, direction: &[f32]) -> &mut Self {
+    fn translate(&mut self, direction: &[T]) -> &mut Self {
         debug_assert!(direction.len() >= 3);
 
         let mut x = direction[0];
@@ -329,7 +332,7 @@ impl Matrix for Mat4 {
         self
     }
 
-    fn rotate(&mut self, angle: f32, axis: &[f32]) -> &mut Self {
+    fn rotate(&mut self, angle: T, axis: &[T]) -> &mut Self {
         debug_assert!(axis.len() >= 3);
 
         let mut x = axis[0];
@@ -345,8 +348,8 @@ impl Matrix for Mat4 {
         let len = (x * x + y * y + z * z).sqrt();
 
         // no rotation around nothing
-        if len.abs() <= EPSILON {
-            debug_assert!(len.abs() > EPSILON);
+        if len.abs() <= T::EPSILON {
+            debug_assert!(len.abs() > T::EPSILON);
             return self;
         }
 
@@ -355,7 +358,7 @@ impl Matrix for Mat4 {
         z /= len;
 
         let (s, c) = angle.sin_cos();
-        let t = 1. - c;
+        let t = T::ONE - c;
 
         let v00 = self[0];
         let v01 = self[1];
@@ -397,176 +400,457 @@ impl Matrix for Mat4 {
 
         self
     }
+
+    fn try_rotate(&mut self, angle: T, axis: &[T]) -> Result<&mut Self, MathError> {
+        check_homogenous_len(axis.len(), 4)?;
+
+        let w = if axis.len() > 3 { axis[3] } else { T::ONE };
+        let len = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if w == T::ZERO {
+            return Err(MathError::ZeroLength);
+        }
+        check_nonzero(len, T::EPSILON * w.abs())?;
+
+        Ok(self.rotate(angle, axis))
+    }
+}
+
+impl<T: Scalar> RotationMatrix for Mat4<T> {
+    fn from_axis_angle(axis: &[T], angle: T) -> Self {
+        let mut m = Self::identity();
+        m.rotate(angle, axis);
+        m
+    }
+
+    fn from_rotation_x(angle: T) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (o, l) = (T::ZERO, T::ONE);
+        [l, o, o, o, o, c, s, o, o, -s, c, o, o, o, o, l]
+    }
+
+    fn from_rotation_y(angle: T) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (o, l) = (T::ZERO, T::ONE);
+        [c, o, -s, o, o, l, o, o, s, o, c, o, o, o, o, l]
+    }
+
+    fn from_rotation_z(angle: T) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (o, l) = (T::ZERO, T::ONE);
+        [c, s, o, o, -s, c, o, o, o, o, l, o, o, o, o, l]
+    }
+
+    #[cfg(feature = "Quaternion")]
+    fn from_quat(q: &Quat<T>) -> Self {
+        q.to_mat4()
+    }
+
+    /// Same as `rotate`, which already respects the axis for `Mat4`
+    fn rotate_axis(&mut self, angle: T, axis: &[T]) -> &mut Self {
+        self.rotate(angle, axis)
+    }
+
+    fn rotate_x(&mut self, angle: T) -> &mut Self {
+        let v10 = self[4];
+        let v11 = self[5];
+        let v12 = self[6];
+        let v13 = self[7];
+        let v20 = self[8];
+        let v21 = self[9];
+        let v22 = self[10];
+        let v23 = self[11];
+
+        let (s, c) = angle.sin_cos();
+
+        self[4] = c * v10 + s * v20;
+        self[5] = c * v11 + s * v21;
+        self[6] = c * v12 + s * v22;
+        self[7] = c * v13 + s * v23;
+        self[8] = c * v20 - s * v10;
+        self[9] = c * v21 - s * v11;
+        self[10] = c * v22 - s * v12;
+        self[11] = c * v23 - s * v13;
+
+        self
+    }
+
+    fn rotate_y(&mut self, angle: T) -> &mut Self {
+        let v00 = self[0];
+        let v01 = self[1];
+        let v02 = self[2];
+        let v03 = self[3];
+        let v20 = self[8];
+        let v21 = self[9];
+        let v22 = self[10];
+        let v23 = self[11];
+
+        let (s, c) = angle.sin_cos();
+
+        self[0] = c * v00 - s * v20;
+        self[1] = c * v01 - s * v21;
+        self[2] = c * v02 - s * v22;
+        self[3] = c * v03 - s * v23;
+        self[8] = s * v00 + c * v20;
+        self[9] = s * v01 + c * v21;
+        self[10] = s * v02 + c * v22;
+        self[11] = s * v03 + c * v23;
+
+        self
+    }
+
+    fn rotate_z(&mut self, angle: T) -> &mut Self {
+        let v00 = self[0];
+        let v01 = self[1];
+        let v02 = self[2];
+        let v03 = self[3];
+        let v10 = self[4];
+        let v11 = self[5];
+        let v12 = self[6];
+        let v13 = self[7];
+
+        let (s, c) = angle.sin_cos();
+
+        self[0] = c * v00 + s * v10;
+        self[1] = c * v01 + s * v11;
+        self[2] = c * v02 + s * v12;
+        self[3] = c * v03 + s * v13;
+        self[4] = c * v10 - s * v00;
+        self[5] = c * v11 - s * v01;
+        self[6] = c * v12 - s * v02;
+        self[7] = c * v13 - s * v03;
+
+        self
+    }
+}
+
+/// Fast paths for affine matrices, i.e. matrices whose last row is `[0, 0, 0, 1]`
+///
+/// The results are undefined if the operands are not affine.
+pub trait AffineMatrix: Matrix {
+    /// Check whether the last row of this matrix is `[0, 0, 0, 1]`
+    fn is_affine(&self) -> bool;
+
+    /// Compute the inverse of this affine matrix. Returns `None` if it is singular.
+    fn inverse_affine(&mut self) -> Option<&mut Self::MatrixType>;
+
+    /// Compute the inverse of this matrix, assuming it only consists of a rotation and a translation
+    fn inverse_rigid(&mut self) -> &mut Self::MatrixType;
+
+    /// Same as `mul`, but for affine operands
+    fn mul_affine(&mut self, rhs: &Self::MatrixType) -> &mut Self::MatrixType;
+}
+
+impl<T: Scalar> AffineMatrix for Mat4<T> {
+    fn is_affine(&self) -> bool {
+        self[3].abs() <= T::EPSILON
+            && self[7].abs() <= T::EPSILON
+            && self[11].abs() <= T::EPSILON
+            && (self[15] - T::ONE).abs() <= T::EPSILON
+    }
+
+    fn inverse_affine(&mut self) -> Option<&mut Self> {
+        debug_assert!(self.is_affine());
+
+        let v00 = self[0];
+        let v01 = self[1];
+        let v02 = self[2];
+        let v10 = self[4];
+        let v11 = self[5];
+        let v12 = self[6];
+        let v20 = self[8];
+        let v21 = self[9];
+        let v22 = self[10];
+
+        let tmp01 = v22 * v11 - v12 * v21;
+        let tmp11 = -v22 * v10 + v12 * v20;
+        let tmp21 = v21 * v10 - v11 * v20;
+
+        let det = v00 * tmp01 + v01 * tmp11 + v02 * tmp21;
+
+        if det.abs() <= T::EPSILON {
+            return None;
+        }
+
+        let det_inv = T::ONE / det;
+
+        self[0] = tmp01 * det_inv;
+        self[1] = (-v22 * v01 + v02 * v21) * det_inv;
+        self[2] = (v12 * v01 - v02 * v11) * det_inv;
+        self[4] = tmp11 * det_inv;
+        self[5] = (v22 * v00 - v02 * v20) * det_inv;
+        self[6] = (-v12 * v00 + v02 * v10) * det_inv;
+        self[8] = tmp21 * det_inv;
+        self[9] = (-v21 * v00 + v01 * v20) * det_inv;
+        self[10] = (v11 * v00 - v01 * v10) * det_inv;
+
+        let x = self[12];
+        let y = self[13];
+        let z = self[14];
+
+        self[12] = -(self[0] * x + self[4] * y + self[8] * z);
+        self[13] = -(self[1] * x + self[5] * y + self[9] * z);
+        self[14] = -(self[2] * x + self[6] * y + self[10] * z);
+
+        Some(self)
+    }
+
+    fn inverse_rigid(&mut self) -> &mut Self {
+        debug_assert!(self.is_affine());
+
+        let v01 = self[1];
+        let v02 = self[2];
+        let v12 = self[6];
+
+        self[1] = self[4];
+        self[2] = self[8];
+        self[4] = v01;
+        self[6] = self[9];
+        self[8] = v02;
+        self[9] = v12;
+
+        let x = self[12];
+        let y = self[13];
+        let z = self[14];
+
+        self[12] = -(self[0] * x + self[4] * y + self[8] * z);
+        self[13] = -(self[1] * x + self[5] * y + self[9] * z);
+        self[14] = -(self[2] * x + self[6] * y + self[10] * z);
+
+        self
+    }
+
+    fn mul_affine(&mut self, rhs: &Self) -> &mut Self {
+        debug_assert!(self.is_affine() && rhs.is_affine());
+
+        let r00 = rhs[0];
+        let r01 = rhs[1];
+        let r02 = rhs[2];
+        let r10 = rhs[4];
+        let r11 = rhs[5];
+        let r12 = rhs[6];
+        let r20 = rhs[8];
+        let r21 = rhs[9];
+        let r22 = rhs[10];
+
+        let mut v0 = self[0];
+        let mut v1 = self[1];
+        let mut v2 = self[2];
+        self[0] = v0 * r00 + v1 * r10 + v2 * r20;
+        self[1] = v0 * r01 + v1 * r11 + v2 * r21;
+        self[2] = v0 * r02 + v1 * r12 + v2 * r22;
+
+        v0 = self[4];
+        v1 = self[5];
+        v2 = self[6];
+        self[4] = v0 * r00 + v1 * r10 + v2 * r20;
+        self[5] = v0 * r01 + v1 * r11 + v2 * r21;
+        self[6] = v0 * r02 + v1 * r12 + v2 * r22;
+
+        v0 = self[8];
+        v1 = self[9];
+        v2 = self[10];
+        self[8] = v0 * r00 + v1 * r10 + v2 * r20;
+        self[9] = v0 * r01 + v1 * r11 + v2 * r21;
+        self[10] = v0 * r02 + v1 * r12 + v2 * r22;
+
+        v0 = self[12];
+        v1 = self[13];
+        v2 = self[14];
+        self[12] = v0 * r00 + v1 * r10 + v2 * r20 + rhs[12];
+        self[13] = v0 * r01 + v1 * r11 + v2 * r21 + rhs[13];
+        self[14] = v0 * r02 + v1 * r12 + v2 * r22 + rhs[14];
+
+        self
+    }
 }
 
-pub trait ProjectionMatrix {
-    fn create_perspective(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> Mat4;
+pub trait ProjectionMatrix: Matrix {
+    fn create_perspective(
+        fov_y: Self::ScalarType,
+        aspect_ratio: Self::ScalarType,
+        near: Self::ScalarType,
+        far: Self::ScalarType,
+    ) -> Self::MatrixType;
     fn create_perspective_from_viewport(
-        vp_left: f32,
-        vp_right: f32,
-        vp_bot: f32,
-        vp_top: f32,
-        near: f32,
-        far: f32,
-    ) -> Mat4;
+        vp_left: Self::ScalarType,
+        vp_right: Self::ScalarType,
+        vp_bot: Self::ScalarType,
+        vp_top: Self::ScalarType,
+        near: Self::ScalarType,
+        far: Self::ScalarType,
+    ) -> Self::MatrixType;
 
     fn create_orthogonal_from_viewport(
-        vp_left: f32,
-        vp_right: f32,
-        vp_bot: f32,
-        vp_top: f32,
-        near: f32,
-        far: f32,
-    ) -> Mat4;
+        vp_left: Self::ScalarType,
+        vp_right: Self::ScalarType,
+        vp_bot: Self::ScalarType,
+        vp_top: Self::ScalarType,
+        near: Self::ScalarType,
+        far: Self::ScalarType,
+    ) -> Self::MatrixType;
 }
 
-impl ProjectionMatrix for Mat4 {
-    fn create_perspective(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
-        let f = 1. / (fov_y / 2.).tan();
-        let nf = 1. / (near - far);
+impl<T: Scalar> ProjectionMatrix for Mat4<T> {
+    fn create_perspective(fov_y: T, aspect_ratio: T, near: T, far: T) -> Self {
+        let two = T::ONE + T::ONE;
+        let f = T::ONE / (fov_y / two).tan();
+        let nf = T::ONE / (near - far);
+        let o = T::ZERO;
         [
             f / aspect_ratio,
-            0.,
-            0.,
-            0.,
-            0.,
+            o,
+            o,
+            o,
+            o,
             f,
-            0.,
-            0.,
-            0.,
-            0.,
+            o,
+            o,
+            o,
+            o,
             (far + near) * nf,
-            -1.,
-            0.,
-            0.,
-            2. * far * near * nf,
-            0.,
+            -T::ONE,
+            o,
+            o,
+            two * far * near * nf,
+            o,
         ]
     }
     fn create_perspective_from_viewport(
-        vp_left: f32,
-        vp_right: f32,
-        vp_bot: f32,
-        vp_top: f32,
-        near: f32,
-        far: f32,
+        vp_left: T,
+        vp_right: T,
+        vp_bot: T,
+        vp_top: T,
+        near: T,
+        far: T,
     ) -> Self {
-        let wi = 1. / (vp_right - vp_left);
-        let hi = 1. / (vp_top - vp_bot);
-        let nf = 1. / (near - far);
+        let two = T::ONE + T::ONE;
+        let wi = T::ONE / (vp_right - vp_left);
+        let hi = T::ONE / (vp_top - vp_bot);
+        let nf = T::ONE / (near - far);
+        let o = T::ZERO;
 
         [
-            near * 2. * wi,
-            0.,
-            0.,
-            0.,
-            0.,
-            near * 2. * hi,
-            0.,
-            0.,
+            near * two * wi,
+            o,
+            o,
+            o,
+            o,
+            near * two * hi,
+            o,
+            o,
             (vp_right + vp_left) * wi,
             (vp_top + vp_bot) * hi,
             (far + near) * nf,
-            -1.,
-            0.,
-            0.,
-            far * near * 2. * nf,
-            0.,
+            -T::ONE,
+            o,
+            o,
+            far * near * two * nf,
+            o,
         ]
     }
 
     fn create_orthogonal_from_viewport(
-        vp_left: f32,
-        vp_right: f32,
-        vp_bot: f32,
-        vp_top: f32,
-        near: f32,
-        far: f32,
+        vp_left: T,
+        vp_right: T,
+        vp_bot: T,
+        vp_top: T,
+        near: T,
+        far: T,
     ) -> Self {
-        let wi = 1. / (vp_right - vp_left);
-        let hi = 1. / (vp_top - vp_bot);
-        let nf = 1. / (near - far);
+        let two = T::ONE + T::ONE;
+        let wi = T::ONE / (vp_right - vp_left);
+        let hi = T::ONE / (vp_top - vp_bot);
+        let nf = T::ONE / (near - far);
+        let o = T::ZERO;
 
         [
-            2. * wi,
-            0.,
-            0.,
-            0.,
-            0.,
-            2. * hi,
-            0.,
-            0.,
-            0.,
-            0.,
-            2. * nf,
-            0.,
+            two * wi,
+            o,
+            o,
+            o,
+            o,
+            two * hi,
+            o,
+            o,
+            o,
+            o,
+            two * nf,
+            o,
             -(vp_left + vp_right) * wi,
             -(vp_top + vp_bot) * hi,
             (far + near) * nf,
-            1.,
+            T::ONE,
         ]
     }
 }
 
 #[cfg(feature = "Vector3")]
-pub trait ViewMatrix {
-    fn look_at_lh(eye: &Vec3, target: &Vec3, up: &Vec3) -> Mat4;
-    fn look_at_rh(eye: &Vec3, target: &Vec3, up: &Vec3) -> Mat4;
+pub trait ViewMatrix: Matrix {
+    fn look_at_lh(
+        eye: &Vec3<Self::ScalarType>,
+        target: &Vec3<Self::ScalarType>,
+        up: &Vec3<Self::ScalarType>,
+    ) -> Self::MatrixType;
+    fn look_at_rh(
+        eye: &Vec3<Self::ScalarType>,
+        target: &Vec3<Self::ScalarType>,
+        up: &Vec3<Self::ScalarType>,
+    ) -> Self::MatrixType;
 }
 
 // Just a helper function
 #[cfg(feature = "Vector3")]
-fn view_matrix(eye: &Vec3, z: &Vec3, up: &Vec3) -> Mat4 {
+fn view_matrix<T: Scalar>(eye: &Vec3<T>, z: &Vec3<T>, up: &Vec3<T>) -> Mat4<T> {
     use crate::vec3::CrossProduct;
     use crate::Vector;
 
     let z_mag = z.mag();
-    if z_mag < EPSILON {
+    if z_mag < T::EPSILON {
         // The eye is on the target, do not transform
         return Mat4::identity();
     }
 
-    let zn = z.scale(1.0 / z_mag);
+    let zn = z.scale(T::ONE / z_mag);
 
     let x = up.cross(&zn);
     let x_mag = x.mag();
-    debug_assert!(x_mag > EPSILON);
+    debug_assert!(x_mag > T::EPSILON);
 
-    let xn = x.scale(1.0 / x_mag);
+    let xn = x.scale(T::ONE / x_mag);
     let yn = zn.cross(&xn);
     [
         xn[0],
         yn[0],
         zn[0],
-        0.0,
+        T::ZERO,
         xn[1],
         yn[1],
         zn[1],
-        0.0,
+        T::ZERO,
         xn[2],
         yn[2],
         zn[2],
-        0.0,
+        T::ZERO,
         -xn.dot(eye),
         -yn.dot(eye),
         -zn.dot(eye),
-        1.0,
+        T::ONE,
     ]
 }
 
 #[cfg(feature = "Vector3")]
-impl ViewMatrix for Mat4 {
-    fn look_at_lh(eye: &Vec3, target: &Vec3, up: &Vec3) -> Self {
+impl<T: Scalar> ViewMatrix for Mat4<T> {
+    fn look_at_lh(eye: &Vec3<T>, target: &Vec3<T>, up: &Vec3<T>) -> Self {
         use crate::Vector;
         let z = target.sub(eye);
-        view_matrix(eye, &z, &up)
+        view_matrix(eye, &z, up)
     }
 
-    fn look_at_rh(eye: &Vec3, target: &Vec3, up: &Vec3) -> Self {
+    fn look_at_rh(eye: &Vec3<T>, target: &Vec3<T>, up: &Vec3<T>) -> Self {
         use crate::Vector;
 
         let z = eye.sub(target);
-        view_matrix(eye, &z, &up)
+        view_matrix(eye, &z, up)
     }
 }
 
@@ -574,22 +858,23 @@ impl ViewMatrix for Mat4 {
 mod tests {
     use super::*;
     use crate::utils::almost_eq;
+    use std::f32;
 
     #[test]
     fn mat4_zeros() {
-        let zeros = Mat4::zeros();
+        let zeros: Mat4 = Mat4::zeros();
         assert!(zeros.iter().all(|&x| x == 0.0));
     }
 
     #[test]
     fn mat4_ones() {
-        let zeros = Mat4::ones();
+        let zeros: Mat4 = Mat4::ones();
         assert!(zeros.iter().all(|&x| x == 1.0));
     }
 
     #[test]
     fn mat4_identity() {
-        let i = Mat4::identity();
+        let i: Mat4 = Mat4::identity();
         assert_eq!(i[0], 1.0);
         assert_eq!(i[1], 0.0);
         assert_eq!(i[2], 0.0);
@@ -613,7 +898,7 @@ mod tests {
 
     #[test]
     fn mat4_copy_to() {
-        let mut a = Mat4::zeros();
+        let mut a: Mat4 = Mat4::zeros();
         let b = Mat4::ones();
 
         b.copy_to(&mut a);
@@ -717,10 +1002,10 @@ mod tests {
 
     #[test]
     fn mat4_inverse_valid() {
-        let mut a = [
+        let mut a: Mat4 = [
             3., 4., 1., 2., 3., 6., 10., 12., 2., 7., 3., 14., 16., 4., 8., 18.,
         ];
-        let b = a.clone();
+        let b = a;
 
         a.inverse().expect("Inverse should exist");
 
@@ -825,6 +1110,113 @@ mod tests {
         assert!(almost_eq(&r, &[-3., -1., 5., 1.]));
     }
 
+    #[test]
+    fn mat4_rotate_xyz_matches_rotate() {
+        let base: Mat4 = [
+            1., 3., 2., 4., 2., 8., 9., 2., 7., 5., 1., 3., 6., 2., 4., 1.,
+        ];
+        let axes = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+        for &angle in &[0.3, -2.1, f32::consts::PI] {
+            let mut fast = [base; 3];
+            fast[0].rotate_x(angle);
+            fast[1].rotate_y(angle);
+            fast[2].rotate_z(angle);
+
+            for (m, axis) in fast.iter().zip(axes.iter()) {
+                let mut full = base;
+                full.rotate(angle, axis);
+                assert!(almost_eq(m, &full));
+            }
+        }
+    }
+
+    #[test]
+    fn mat4_from_rotation() {
+        let angle = 1.1;
+        let built = [
+            Mat4::from_rotation_x(angle),
+            Mat4::from_rotation_y(angle),
+            Mat4::from_rotation_z(angle),
+        ];
+        let axes = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+
+        for (m, axis) in built.iter().zip(axes.iter()) {
+            let mut full = Mat4::identity();
+            full.rotate(angle, axis);
+            assert!(almost_eq(m, &full));
+            assert!(almost_eq(m, &Mat4::from_axis_angle(axis, angle)));
+        }
+    }
+
+    #[cfg(feature = "Quaternion")]
+    #[test]
+    fn mat4_from_quat() {
+        let axis = [0.5, 2., -1.];
+        let q = Quat::from_axis_angle(&axis, -0.4);
+        let m: Mat4 = Mat4::from_axis_angle(&axis, -0.4);
+        assert!(almost_eq(&Mat4::from_quat(&q), &m));
+    }
+
+    fn affine_sample() -> Mat4 {
+        let mut m = Mat4::identity();
+        m.translate(&[3., -5., 7.])
+            .rotate(0.8, &[1., 2., -1.])
+            .scale(2.);
+        m[15] = 1.;
+        m[4] += 0.5;
+        m
+    }
+
+    #[test]
+    fn mat4_is_affine() {
+        assert!(affine_sample().is_affine());
+        assert!(!Mat4::create_perspective(1., 1., 0.1, 10.).is_affine());
+    }
+
+    #[test]
+    fn mat4_inverse_affine() {
+        let m = affine_sample();
+
+        let mut a = m;
+        let mut b = m;
+        a.inverse_affine().expect("Inverse should exist");
+        b.inverse().expect("Inverse should exist");
+        assert!(almost_eq(&a, &b));
+
+        let mut singular = m;
+        singular[0] = 0.;
+        singular[1] = 0.;
+        singular[2] = 0.;
+        assert_eq!(singular.inverse_affine(), None);
+    }
+
+    #[test]
+    fn mat4_inverse_rigid() {
+        let mut m = Mat4::identity();
+        m.translate(&[3., -5., 7.]).rotate(-1.3, &[0., 2., 1.]);
+
+        let mut a = m;
+        let mut b = m;
+        a.inverse_rigid();
+        b.inverse().expect("Inverse should exist");
+        assert!(almost_eq(&a, &b));
+    }
+
+    #[test]
+    fn mat4_mul_affine() {
+        let a = affine_sample();
+        let mut b = Mat4::identity();
+        b.translate(&[-1., 2., 0.5]).rotate(2.1, &[0., 0., 1.]);
+
+        let mut fast = a;
+        let mut full = a;
+        fast.mul_affine(&b);
+        full.mul(&b);
+        assert!(almost_eq(&fast, &full));
+        assert!(fast.is_affine());
+    }
+
     #[test]
     fn mat4_look_at_rh() {
         let eye = [0., 0., 1.];
@@ -839,7 +1231,7 @@ mod tests {
         // Test case where the eye is on the target
         assert_eq!(
             Mat4::look_at_rh(&[0., 0., 0.], &target, &up),
-            Mat4::identity()
+            Mat4::<f32>::identity()
         );
     }
 
@@ -859,7 +1251,7 @@ mod tests {
         // Test case where the eye is on the target
         assert_eq!(
             Mat4::look_at_rh(&[0., 0., 0.], &target, &up),
-            Mat4::identity()
+            Mat4::<f32>::identity()
         );
     }
 }
@@ -1,7 +1,17 @@
+use crate::error::{check_homogenous_len, MathError};
+use crate::scalar::Scalar;
+
+#[cfg(feature = "Quaternion")]
+use crate::quat::Quat;
+
 /// The base Matrix trait
 pub trait Matrix {
     type MatrixType;
     type VectorType;
+    type ScalarType: Scalar;
+
+    /// The number of rows and columns
+    const DIMENSION: usize;
 
     /// Create a matrix filled with zeros
     fn zeros() -> Self::MatrixType;
@@ -25,13 +35,13 @@ pub trait Matrix {
     ///
     /// Depending on dimensionality, the homogenous coordinate can be omitted,
     /// if so, it will be assumed to be equal to 1.
-    fn mul_vector(&self, rhs: &[f32]) -> Self::VectorType;
+    fn mul_vector(&self, rhs: &[Self::ScalarType]) -> Self::VectorType;
 
     /// Multiplies the given row vector with this matrix, i.e. `lhs * Matrix`
     ///
     /// Depending on dimensionality, the homogenous coordinate can be omitted,
     /// if so, it will be assumed to be equal to 1.
-    fn mul_vector_left(&self, lhs: &[f32]) -> Self::VectorType;
+    fn mul_vector_left(&self, lhs: &[Self::ScalarType]) -> Self::VectorType;
 
     /// Perform element-wise addition with the given right-hand-side operand
     fn add(&mut self, rhs: &Self::MatrixType) -> &mut Self::MatrixType;
@@ -39,13 +49,13 @@ pub trait Matrix {
     fn sub(&mut self, rhs: &Self::MatrixType) -> &mut Self::MatrixType;
 
     /// Scale the matrix elment-wise by the given constant
-    fn scale(&mut self, factor: f32) -> &mut Self::MatrixType;
+    fn scale(&mut self, factor: Self::ScalarType) -> &mut Self::MatrixType;
 
     /// Compute the inverse of this matrix. Returns `None` if it is singular.
     fn inverse(&mut self) -> Option<&mut Self::MatrixType>;
 
     /// Compute the determinant of this matrix.
-    fn det(&self) -> f32;
+    fn det(&self) -> Self::ScalarType;
 
     /// Compute the adjugate of this matrix
     fn adjugate(&mut self) -> &mut Self::MatrixType;
@@ -54,11 +64,91 @@ pub trait Matrix {
     ///
     /// Depending on dimensionality, the homogenous coordinate of `direction` can be omitted,
     /// if so, it will be assumed to be equal to 1.
-    fn translate(&mut self, direction: &[f32]) -> &mut Self::MatrixType;
+    fn translate(&mut self, direction: &[Self::ScalarType]) -> &mut Self::MatrixType;
 
     /// Rotate this matrix by the given angle (radians) around the given axis
     ///
     /// Depending on dimensionality, the homogenous coordinate of `axis` can be omitted,
     /// if so, it will be assumed to be equal to 1.
-    fn rotate(&mut self, angle: f32, axis: &[f32]) -> &mut Self::MatrixType;
+    fn rotate(
+        &mut self,
+        angle: Self::ScalarType,
+        axis: &[Self::ScalarType],
+    ) -> &mut Self::MatrixType;
+
+    /// Same as `mul_vector`, but fails if `rhs` has the wrong length
+    fn try_mul_vector(&self, rhs: &[Self::ScalarType]) -> Result<Self::VectorType, MathError> {
+        check_homogenous_len(rhs.len(), Self::DIMENSION)?;
+        Ok(self.mul_vector(rhs))
+    }
+
+    /// Same as `mul_vector_left`, but fails if `lhs` has the wrong length
+    fn try_mul_vector_left(&self, lhs: &[Self::ScalarType]) -> Result<Self::VectorType, MathError> {
+        check_homogenous_len(lhs.len(), Self::DIMENSION)?;
+        Ok(self.mul_vector_left(lhs))
+    }
+
+    /// Same as `inverse`, but fails with `MathError::Singular` instead of returning `None`
+    fn try_inverse(&mut self) -> Result<&mut Self::MatrixType, MathError> {
+        self.inverse().ok_or(MathError::Singular)
+    }
+
+    /// Same as `translate`, but fails if `direction` has the wrong length
+    fn try_translate(
+        &mut self,
+        direction: &[Self::ScalarType],
+    ) -> Result<&mut Self::MatrixType, MathError> {
+        check_homogenous_len(direction.len(), Self::DIMENSION)?;
+        Ok(self.translate(direction))
+    }
+
+    /// Same as `rotate`, but fails if `axis` has the wrong length or cannot be normalized
+    fn try_rotate(
+        &mut self,
+        angle: Self::ScalarType,
+        axis: &[Self::ScalarType],
+    ) -> Result<&mut Self::MatrixType, MathError> {
+        check_homogenous_len(axis.len(), Self::DIMENSION)?;
+        Ok(self.rotate(angle, axis))
+    }
+}
+
+/// Rotations of a matrix interpreted as a linear 3D transform
+///
+/// Unlike `Matrix::rotate` for `Mat3`, which treats the matrix as a 2D homogenous
+/// transform, all rotations here are around 3D axes through the origin.
+pub trait RotationMatrix: Matrix {
+    /// Create a rotation by the given angle (radians) around the given axis
+    ///
+    /// The axis does not need to be normalized. A zero-length axis results in the identity.
+    fn from_axis_angle(axis: &[Self::ScalarType], angle: Self::ScalarType) -> Self::MatrixType;
+
+    /// Create a rotation by the given angle (radians) around the X-axis
+    fn from_rotation_x(angle: Self::ScalarType) -> Self::MatrixType;
+
+    /// Create a rotation by the given angle (radians) around the Y-axis
+    fn from_rotation_y(angle: Self::ScalarType) -> Self::MatrixType;
+
+    /// Create a rotation by the given angle (radians) around the Z-axis
+    fn from_rotation_z(angle: Self::ScalarType) -> Self::MatrixType;
+
+    /// Create a rotation from the given unit quaternion
+    #[cfg(feature = "Quaternion")]
+    fn from_quat(q: &Quat<Self::ScalarType>) -> Self::MatrixType;
+
+    /// Rotate this matrix by the given angle (radians) around the given 3D axis
+    fn rotate_axis(
+        &mut self,
+        angle: Self::ScalarType,
+        axis: &[Self::ScalarType],
+    ) -> &mut Self::MatrixType;
+
+    /// Rotate this matrix by the given angle (radians) around the X-axis
+    fn rotate_x(&mut self, angle: Self::ScalarType) -> &mut Self::MatrixType;
+
+    /// Rotate this matrix by the given angle (radians) around the Y-axis
+    fn rotate_y(&mut self, angle: Self::ScalarType) -> &mut Self::MatrixType;
+
+    /// Rotate this matrix by the given angle (radians) around the Z-axis
+    fn rotate_z(&mut self, angle: Self::ScalarType) -> &mut Self::MatrixType;
 }
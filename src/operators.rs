@@ -0,0 +1,410 @@
+//! Operator overloading newtypes over the plain array types.
+//!
+//! The wrappers are `#[repr(transparent)]`, so they have the exact same memory layout as the
+//! wrapped arrays. Operators follow the semantics of the `Matrix` and `Vector` traits, i.e.
+//! `a * b` for two matrices is the same as `a.mul(&b)` and `m * v` is the same as `m.mul_vector(&v)`.
+//!
+//! ```rust
+//! use webgl_matrix::{Matrix, Mat4, M4, V4};
+//!
+//! let a = V4([1., 2., 3., 1.]);
+//! let b = V4([2., 2., 2., 0.]);
+//!
+//! let c = (a + b * 2.) - a;
+//! assert_eq!(c, V4([4., 4., 4., 0.]));
+//!
+//! let m = M4(Mat4::identity());
+//! assert_eq!(m * c, c);
+//! ```
+
+#[cfg(feature = "Matrix3")]
+use crate::mat3::Mat3;
+#[cfg(feature = "Matrix4")]
+use crate::mat4::Mat4;
+#[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
+use crate::matrix::Matrix;
+#[cfg(any(feature = "Vector3", feature = "Vector4"))]
+use crate::scalar::Scalar;
+#[cfg(feature = "Vector3")]
+use crate::vec3::Vec3;
+#[cfg(feature = "Vector4")]
+use crate::vec4::Vec4;
+#[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
+use crate::vector::MulVectorMatrix;
+#[cfg(any(feature = "Vector3", feature = "Vector4"))]
+use crate::vector::Vector;
+#[cfg(any(feature = "Vector3", feature = "Vector4"))]
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+#[cfg(any(feature = "Vector3", feature = "Vector4"))]
+macro_rules! impl_wrapper {
+    ($name:ident, $inner:ident) => {
+        impl<T: Scalar> From<$inner<T>> for $name<T> {
+            #[inline]
+            fn from(inner: $inner<T>) -> Self {
+                $name(inner)
+            }
+        }
+
+        impl<T: Scalar> From<$name<T>> for $inner<T> {
+            #[inline]
+            fn from(wrapper: $name<T>) -> Self {
+                wrapper.0
+            }
+        }
+
+        impl<T: Scalar> AsRef<$inner<T>> for $name<T> {
+            #[inline]
+            fn as_ref(&self) -> &$inner<T> {
+                &self.0
+            }
+        }
+
+        impl<T: Scalar> AsMut<$inner<T>> for $name<T> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut $inner<T> {
+                &mut self.0
+            }
+        }
+
+        impl<T: Scalar> AsRef<[T]> for $name<T> {
+            #[inline]
+            fn as_ref(&self) -> &[T] {
+                &self.0
+            }
+        }
+
+        impl<T: Scalar> AsMut<[T]> for $name<T> {
+            #[inline]
+            fn as_mut(&mut self) -> &mut [T] {
+                &mut self.0
+            }
+        }
+
+        impl<T: Scalar> Index<usize> for $name<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, index: usize) -> &T {
+                &self.0[index]
+            }
+        }
+
+        impl<T: Scalar> IndexMut<usize> for $name<T> {
+            #[inline]
+            fn index_mut(&mut self, index: usize) -> &mut T {
+                &mut self.0[index]
+            }
+        }
+
+        impl<T: Scalar> Neg for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn neg(mut self) -> $name<T> {
+                for v in self.0.iter_mut() {
+                    *v = -*v;
+                }
+                self
+            }
+        }
+
+        impl<T: Scalar> Add for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn add(mut self, rhs: $name<T>) -> $name<T> {
+                self += rhs;
+                self
+            }
+        }
+
+        impl<T: Scalar> AddAssign for $name<T> {
+            #[inline]
+            fn add_assign(&mut self, rhs: $name<T>) {
+                for (l, &r) in self.0.iter_mut().zip(rhs.0.iter()) {
+                    *l += r;
+                }
+            }
+        }
+
+        impl<T: Scalar> Sub for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn sub(mut self, rhs: $name<T>) -> $name<T> {
+                self -= rhs;
+                self
+            }
+        }
+
+        impl<T: Scalar> SubAssign for $name<T> {
+            #[inline]
+            fn sub_assign(&mut self, rhs: $name<T>) {
+                for (l, &r) in self.0.iter_mut().zip(rhs.0.iter()) {
+                    *l -= r;
+                }
+            }
+        }
+
+        impl<T: Scalar> Mul<T> for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn mul(mut self, rhs: T) -> $name<T> {
+                self *= rhs;
+                self
+            }
+        }
+
+        impl<T: Scalar> MulAssign<T> for $name<T> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: T) {
+                for v in self.0.iter_mut() {
+                    *v *= rhs;
+                }
+            }
+        }
+
+        impl<T: Scalar> Div<T> for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn div(mut self, rhs: T) -> $name<T> {
+                self /= rhs;
+                self
+            }
+        }
+
+        impl<T: Scalar> DivAssign<T> for $name<T> {
+            #[inline]
+            fn div_assign(&mut self, rhs: T) {
+                for v in self.0.iter_mut() {
+                    *v /= rhs;
+                }
+            }
+        }
+
+        impl Mul<$name<f32>> for f32 {
+            type Output = $name<f32>;
+
+            #[inline]
+            fn mul(self, rhs: $name<f32>) -> $name<f32> {
+                rhs * self
+            }
+        }
+
+        impl Mul<$name<f64>> for f64 {
+            type Output = $name<f64>;
+
+            #[inline]
+            fn mul(self, rhs: $name<f64>) -> $name<f64> {
+                rhs * self
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "Vector3", feature = "Vector4"))]
+macro_rules! impl_vector_wrapper {
+    ($name:ident, $inner:ident) => {
+        impl_wrapper!($name, $inner);
+
+        /// Element-wise multiplication
+        impl<T: Scalar> Mul for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn mul(self, rhs: $name<T>) -> $name<T> {
+                $name(self.0.mul(&rhs.0))
+            }
+        }
+
+        /// Element-wise multiplication
+        impl<T: Scalar> MulAssign for $name<T> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $name<T>) {
+                self.0 = self.0.mul(&rhs.0);
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
+macro_rules! impl_matrix_wrapper {
+    ($name:ident, $inner:ident, $vector:ident) => {
+        impl_wrapper!($name, $inner);
+
+        /// Matrix multiplication, same as `Matrix::mul`
+        impl<T: Scalar> Mul for $name<T> {
+            type Output = $name<T>;
+
+            #[inline]
+            fn mul(mut self, rhs: $name<T>) -> $name<T> {
+                self.0.mul(&rhs.0);
+                self
+            }
+        }
+
+        /// Matrix multiplication, same as `Matrix::mul`
+        impl<T: Scalar> MulAssign for $name<T> {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $name<T>) {
+                self.0.mul(&rhs.0);
+            }
+        }
+
+        /// Matrix-vector multiplication, same as `Matrix::mul_vector`
+        impl<T: Scalar> Mul<$vector<T>> for $name<T> {
+            type Output = $vector<T>;
+
+            #[inline]
+            fn mul(self, rhs: $vector<T>) -> $vector<T> {
+                $vector(self.0.mul_vector(&rhs.0))
+            }
+        }
+
+        /// Vector-matrix multiplication, same as `Matrix::mul_vector_left`
+        impl<T: Scalar> Mul<$name<T>> for $vector<T> {
+            type Output = $vector<T>;
+
+            #[inline]
+            fn mul(self, rhs: $name<T>) -> $vector<T> {
+                $vector(self.0.mul_matrix(&rhs.0))
+            }
+        }
+    };
+}
+
+/// Operator overloading wrapper around `Vec3`
+#[cfg(feature = "Vector3")]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct V3<T = f32>(pub Vec3<T>);
+
+#[cfg(feature = "Vector3")]
+impl_vector_wrapper!(V3, Vec3);
+
+/// Operator overloading wrapper around `Vec4`
+#[cfg(feature = "Vector4")]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct V4<T = f32>(pub Vec4<T>);
+
+#[cfg(feature = "Vector4")]
+impl_vector_wrapper!(V4, Vec4);
+
+/// Operator overloading wrapper around `Mat3`
+#[cfg(feature = "Matrix3")]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct M3<T = f32>(pub Mat3<T>);
+
+#[cfg(feature = "Matrix3")]
+impl_matrix_wrapper!(M3, Mat3, V3);
+
+/// Operator overloading wrapper around `Mat4`
+#[cfg(feature = "Matrix4")]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct M4<T = f32>(pub Mat4<T>);
+
+#[cfg(feature = "Matrix4")]
+impl_matrix_wrapper!(M4, Mat4, V4);
+
+// all tests need `V3` or `M4`
+#[cfg(all(test, any(feature = "Vector3", feature = "Matrix4")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "Vector3")]
+    fn operators_vector() {
+        let a = V3([1., 2., 3.]);
+        let b = V3([4., 5., 6.]);
+
+        assert_eq!(a + b, V3([5., 7., 9.]));
+        assert_eq!(b - a, V3([3., 3., 3.]));
+        assert_eq!(a * b, V3([4., 10., 18.]));
+        assert_eq!(a * 2., V3([2., 4., 6.]));
+        assert_eq!(2. * a, V3([2., 4., 6.]));
+        assert_eq!(b / 2., V3([2., 2.5, 3.]));
+        assert_eq!(-a, V3([-1., -2., -3.]));
+        assert_eq!(a[1], 2.);
+    }
+
+    #[test]
+    #[cfg(feature = "Vector3")]
+    fn operators_vector_assign() {
+        let mut a = V3([1., 2., 3.]);
+
+        a += V3([1., 1., 1.]);
+        assert_eq!(a, V3([2., 3., 4.]));
+        a -= V3([2., 2., 2.]);
+        assert_eq!(a, V3([0., 1., 2.]));
+        a *= 3.;
+        assert_eq!(a, V3([0., 3., 6.]));
+        a *= V3([1., 2., 0.5]);
+        assert_eq!(a, V3([0., 6., 3.]));
+        a /= 3.;
+        assert_eq!(a, V3([0., 2., 1.]));
+        a[0] = 5.;
+        assert_eq!(a, V3([5., 2., 1.]));
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix4")]
+    fn operators_matrix() {
+        let a: Mat4 = [
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 13., 14., 15., 16.,
+        ];
+        let b: Mat4 = [
+            11., 12., 13., 14., 15., 16., 17., 18., 19., 20., 21., 22., 23., 24., 25., 26.,
+        ];
+        let v: Vec4 = [17., 18., 19., 20.];
+
+        let mut ab = a;
+        ab.mul(&b);
+        assert_eq!(M4(a) * M4(b), M4(ab));
+
+        let mut m = M4(a);
+        m *= M4(b);
+        assert_eq!(m, M4(ab));
+
+        assert_eq!(M4(a) * V4(v), V4(a.mul_vector(&v)));
+        assert_eq!(V4(v) * M4(a), V4(a.mul_vector_left(&v)));
+
+        let mut a2 = a;
+        a2.scale(2.);
+        assert_eq!((M4(a) + M4(b) - M4(b)) * 2., M4(a2));
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix3")]
+    fn operators_matrix3() {
+        let a: Mat3 = [1., 2., 3., 4., 5., 6., 7., 8., 9.];
+        let v: Vec3 = [11., 12., 13.];
+
+        assert_eq!(M3(a) * V3(v), V3([74., 182., 290.]));
+        assert_eq!(V3(v) * M3(a), V3([150., 186., 222.]));
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix4")]
+    fn operators_conversions() {
+        use std::mem;
+
+        let a: Mat4 = Mat4::identity();
+        let w: M4 = a.into();
+        let b: Mat4 = w.into();
+        assert_eq!(a, b);
+
+        let s: &[f32] = w.as_ref();
+        assert_eq!(s.len(), 16);
+
+        assert_eq!(mem::size_of::<M4>(), mem::size_of::<Mat4>());
+        assert_eq!(mem::align_of::<M4>(), mem::align_of::<Mat4>());
+    }
+}
@@ -0,0 +1,282 @@
+use crate::matrix::Matrix;
+use crate::scalar::Scalar;
+use crate::utils::almost_eq;
+
+#[cfg(feature = "Matrix3")]
+use crate::mat3::Mat3;
+#[cfg(feature = "Matrix4")]
+use crate::mat4::Mat4;
+
+/// The maximum number of iterations of the polar decomposition
+const MAX_POLAR_ITERATIONS: usize = 32;
+
+/// Cleanup of the rotation part of a matrix, e.g. to counter floating point drift
+///
+/// The rotation part of a `Mat4` is its upper left 3x3 block, the translation is left untouched.
+pub trait OrthonormalMatrix: Matrix {
+    /// Make the columns of the rotation part orthonormal using the Gram-Schmidt process
+    ///
+    /// The X-axis keeps its direction and any scaling is removed. Returns `None` and leaves the
+    /// matrix untouched if the columns are linearly dependent.
+    fn orthonormalize(&mut self) -> Option<&mut Self::MatrixType>;
+
+    /// Check whether the columns of the rotation part are orthonormal up to `Scalar::EPSILON`
+    fn is_orthonormal(&self) -> bool;
+
+    /// Check whether the rotation part is orthonormal and not a reflection
+    fn is_rotation(&self) -> bool;
+
+    /// Replace the rotation part with the closest rotation using the polar decomposition
+    ///
+    /// Unlike `orthonormalize` this distributes the error evenly across all axes.
+    /// Returns `None` and leaves the matrix untouched if the rotation part is singular or
+    /// contains a reflection.
+    fn nearest_rotation(&mut self) -> Option<&mut Self::MatrixType>;
+}
+
+pub(crate) type Columns<T> = [[T; 3]; 3];
+
+pub(crate) fn dot<T: Scalar>(a: &[T; 3], b: &[T; 3]) -> T {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn cross<T: Scalar>(a: &[T; 3], b: &[T; 3]) -> [T; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn gram_schmidt<T: Scalar>(c: &Columns<T>) -> Option<Columns<T>> {
+    let mut out = *c;
+    for i in 0..3 {
+        for j in 0..i {
+            let prev = out[j];
+            let d = dot(&out[i], &prev);
+            for (x, &p) in out[i].iter_mut().zip(prev.iter()) {
+                *x -= d * p;
+            }
+        }
+
+        // relative to the original length, so uniformly scaled bases are accepted
+        let len = dot(&out[i], &out[i]).sqrt();
+        if len <= T::EPSILON * dot(&c[i], &c[i]).sqrt() {
+            return None;
+        }
+        for x in &mut out[i] {
+            *x /= len;
+        }
+    }
+    Some(out)
+}
+
+fn gram<T: Scalar>(c: &Columns<T>) -> [T; 9] {
+    let mut out = [T::ZERO; 9];
+    for i in 0..3 {
+        for j in 0..3 {
+            out[3 * i + j] = dot(&c[i], &c[j]);
+        }
+    }
+    out
+}
+
+fn is_orthonormal<T: Scalar>(c: &Columns<T>) -> bool {
+    let (o, l) = (T::ZERO, T::ONE);
+    almost_eq(&gram(c), &[l, o, o, o, l, o, o, o, l])
+}
+
+fn is_rotation<T: Scalar>(c: &Columns<T>) -> bool {
+    is_orthonormal(c) && dot(&c[0], &cross(&c[1], &c[2])) > T::ZERO
+}
+
+// Higham's iteration `R = (R + R^-T) / 2`, where the columns of `R^-T` are the
+// cross products of the columns of `R` divided by the determinant. The determinant is
+// compared to the product of the column lengths, its upper bound, so the check is independent
+// of the scale.
+fn polar_rotation<T: Scalar>(c: &Columns<T>) -> Option<Columns<T>> {
+    let half = T::from_f32(0.5);
+    let mut r = *c;
+
+    for _ in 0..MAX_POLAR_ITERATIONS {
+        let cof = [
+            cross(&r[1], &r[2]),
+            cross(&r[2], &r[0]),
+            cross(&r[0], &r[1]),
+        ];
+        let det = dot(&r[0], &cof[0]);
+        let bound = (dot(&r[0], &r[0]) * dot(&r[1], &r[1]) * dot(&r[2], &r[2])).sqrt();
+        if det <= T::EPSILON * bound {
+            return None;
+        }
+
+        let mut change = T::ZERO;
+        for i in 0..3 {
+            for k in 0..3 {
+                let next = half * (r[i][k] + cof[i][k] / det);
+                change += (next - r[i][k]).abs();
+                r[i][k] = next;
+            }
+        }
+
+        if change <= T::EPSILON {
+            break;
+        }
+    }
+    Some(r)
+}
+
+macro_rules! impl_orthonormal {
+    ($type:ident, $stride:expr) => {
+        impl<T: Scalar> OrthonormalMatrix for $type<T> {
+            fn orthonormalize(&mut self) -> Option<&mut Self> {
+                let c = gram_schmidt(&columns(self, $stride))?;
+                set_columns(self, $stride, &c);
+                Some(self)
+            }
+
+            fn is_orthonormal(&self) -> bool {
+                is_orthonormal(&columns(self, $stride))
+            }
+
+            fn is_rotation(&self) -> bool {
+                is_rotation(&columns(self, $stride))
+            }
+
+            fn nearest_rotation(&mut self) -> Option<&mut Self> {
+                let c = polar_rotation(&columns(self, $stride))?;
+                set_columns(self, $stride, &c);
+                Some(self)
+            }
+        }
+    };
+}
+
+pub(crate) fn columns<T: Scalar>(m: &[T], stride: usize) -> Columns<T> {
+    let col = |i: usize| [m[i * stride], m[i * stride + 1], m[i * stride + 2]];
+    [col(0), col(1), col(2)]
+}
+
+pub(crate) fn set_columns<T: Scalar>(m: &mut [T], stride: usize, c: &Columns<T>) {
+    for (i, col) in c.iter().enumerate() {
+        m[i * stride..i * stride + 3].copy_from_slice(col);
+    }
+}
+
+#[cfg(feature = "Matrix3")]
+impl_orthonormal!(Mat3, 3);
+#[cfg(feature = "Matrix4")]
+impl_orthonormal!(Mat4, 4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "Matrix4")]
+    use crate::utils::almost_eq_relative;
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn orthonormal_mat3_drift() {
+        let mut m: Mat3 = Mat3::identity();
+        for i in 0..1000 {
+            m.rotate(0.01, &[]);
+            // simulate rounding errors accumulating as skew
+            m[i % 9] += 1e-4;
+        }
+        assert!(!m.is_orthonormal());
+
+        let skewed = m;
+        m.orthonormalize().expect("Columns are independent");
+        assert!(m.is_orthonormal());
+        assert!(m.is_rotation());
+
+        // the X-axis keeps its direction
+        let len = (skewed[0] * skewed[0] + skewed[1] * skewed[1] + skewed[2] * skewed[2]).sqrt();
+        assert!(almost_eq(
+            &m[..3],
+            &[skewed[0] / len, skewed[1] / len, skewed[2] / len]
+        ));
+    }
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn orthonormal_mat3_checks() {
+        let reflection: Mat3 = [-1., 0., 0., 0., 1., 0., 0., 0., 1.];
+        assert!(reflection.is_orthonormal());
+        assert!(!reflection.is_rotation());
+
+        let mut scaled: Mat3 = Mat3::identity();
+        scaled.scale(2.);
+        assert!(!scaled.is_orthonormal());
+
+        let mut dependent: Mat3 = [1., 2., 3., 2., 4., 6., 0., 0., 1.];
+        assert_eq!(dependent.orthonormalize(), None);
+    }
+
+    #[cfg(feature = "Matrix3")]
+    #[test]
+    fn orthonormal_mat3_small_scale() {
+        use crate::matrix::RotationMatrix;
+
+        let rotation: Mat3 = Mat3::from_axis_angle(&[1., 2., 3.], 0.7);
+        for &factor in &[0.01, 0.001] {
+            let mut m = rotation;
+            m.scale(factor);
+            m.nearest_rotation().expect("Not singular");
+            assert!(almost_eq(&m, &rotation));
+        }
+
+        let mut flat: Mat3 = rotation;
+        flat[6..].copy_from_slice(&[0., 0., 0.]);
+        flat.scale(0.01);
+        assert_eq!(flat.nearest_rotation(), None);
+
+        let mut m = rotation;
+        m.scale(1e-6);
+        m.orthonormalize().expect("Independent columns");
+        assert!(almost_eq(&m, &rotation));
+
+        let mut dependent: Mat3 = [1., 2., 3., 2., 4., 6., 0., 0., 1.];
+        dependent.scale(1e-6);
+        assert_eq!(dependent.orthonormalize(), None);
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn orthonormal_mat4_nearest_rotation() {
+        let mut rotation: Mat4 = Mat4::identity();
+        rotation.rotate(0.7, &[1., 2., 3.]);
+
+        let mut m = rotation;
+        m.translate(&[3., -5., 7.]);
+        m[1] += 0.01;
+        m[6] -= 0.02;
+        m[8] += 0.01;
+        assert!(!m.is_rotation());
+
+        let translation = [m[12], m[13], m[14], m[15]];
+        m.nearest_rotation().expect("Not singular");
+        assert!(m.is_rotation());
+        assert_eq!(&m[12..], &translation);
+        assert!(almost_eq_relative(&m[..12], &rotation[..12], 0.02, 0.));
+
+        let mut reflection = rotation;
+        reflection[0] = -reflection[0];
+        reflection[1] = -reflection[1];
+        reflection[2] = -reflection[2];
+        assert_eq!(reflection.nearest_rotation(), None);
+    }
+
+    #[cfg(feature = "Matrix4")]
+    #[test]
+    fn orthonormal_mat4_orthonormalize() {
+        let mut m: Mat4 = Mat4::identity();
+        m.rotate(-1.2, &[0., 1., 1.]).scale(3.);
+        m[15] = 1.;
+        m[4] += 0.3;
+
+        m.orthonormalize().expect("Columns are independent");
+        assert!(m.is_rotation());
+        assert_eq!(m[15], 1.);
+    }
+}
@@ -1,16 +1,44 @@
 #[cfg(feature = "SliceOps")]
 pub use crate::slice_ops;
 
-#[cfg(feature = "Vector3")]
-pub use crate::Vec3;
+#[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+pub use crate::Trs;
+#[cfg(feature = "Vector2")]
+pub use crate::Vec2;
 #[cfg(feature = "Vector4")]
 pub use crate::Vec4;
-#[cfg(any(feature = "Vector3", feature = "Vector4"))]
+#[cfg(any(feature = "Vector2", feature = "Vector3", feature = "Vector4"))]
 pub use crate::Vector;
+#[cfg(feature = "IntVector")]
+pub use crate::{BoolVector, CompareVector, IntVector, Select, ToIntVector};
+#[cfg(feature = "Vector3")]
+pub use crate::{CrossProduct, Vec3};
+#[cfg(feature = "Quaternion")]
+pub use crate::{DualQuat, DualQuaternion, Quat, Quaternion};
 
-#[cfg(feature = "Matrix3")]
-pub use crate::Mat3;
+pub use crate::MathError;
+pub use crate::Scalar;
+
+#[cfg(any(
+    feature = "mint",
+    feature = "glam",
+    feature = "nalgebra",
+    feature = "cgmath"
+))]
+pub use crate::Convert;
+#[cfg(all(feature = "Matrix4", feature = "Vector3"))]
+pub use crate::ViewMatrix;
+#[cfg(all(feature = "Operators", feature = "Matrix3"))]
+pub use crate::M3;
+#[cfg(all(feature = "Operators", feature = "Matrix4"))]
+pub use crate::M4;
+#[cfg(all(feature = "Operators", feature = "Vector3"))]
+pub use crate::V3;
+#[cfg(all(feature = "Operators", feature = "Vector4"))]
+pub use crate::V4;
 #[cfg(feature = "Matrix4")]
-pub use crate::{Mat4, ProjectionMatrix};
+pub use crate::{AffineMatrix, Mat4, ProjectionMatrix};
+#[cfg(feature = "Matrix3")]
+pub use crate::{Decomposition, Mat3, Transform2D};
 #[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
-pub use crate::{Matrix, MulVectorMatrix};
+pub use crate::{LuDecomposition, Matrix, MulVectorMatrix, OrthonormalMatrix, RotationMatrix};
@@ -0,0 +1,381 @@
+use crate::error::{check_len, check_nonzero, MathError};
+#[cfg(feature = "Matrix3")]
+use crate::mat3::Mat3;
+#[cfg(feature = "Matrix4")]
+use crate::mat4::Mat4;
+use crate::scalar::Scalar;
+use crate::vec3::Vec3;
+
+/// A quaternion stored as `[x, y, z, w]`, where `w` is the real part
+pub type Quat<T = f32> = [T; 4];
+
+/// Quaternion operations for rotations
+///
+/// The matrix conversions use the same (column-major) convention as `Matrix::rotate`.
+pub trait Quaternion {
+    type QuaternionType;
+    type ScalarType: Scalar;
+
+    /// Create the identity rotation
+    fn identity() -> Self::QuaternionType;
+
+    /// Create a rotation by the given angle (radians) around the given axis
+    ///
+    /// The axis does not need to be normalized.
+    fn from_axis_angle(axis: &[Self::ScalarType], angle: Self::ScalarType) -> Self::QuaternionType;
+
+    /// Compute the Hamilton product `self * rhs`, i.e. the rotation `rhs` followed by `self`
+    fn mul_quat(&self, rhs: &Self::QuaternionType) -> Self::QuaternionType;
+
+    /// Compute the conjugate, which is the inverse rotation for unit quaternions
+    fn conjugate(&self) -> Self::QuaternionType;
+
+    /// Scale this quaternion to unit length
+    fn normalize(&self) -> Self::QuaternionType;
+
+    /// Rotate the given 3-dimensional vector
+    fn rotate_vector(&self, v: &[Self::ScalarType]) -> Vec3<Self::ScalarType>;
+
+    /// Spherical linear interpolation between this rotation (`t = 0`) and `other` (`t = 1`)
+    ///
+    /// Both operands have to be normalized. The shortest path is taken.
+    fn slerp(&self, other: &Self::QuaternionType, t: Self::ScalarType) -> Self::QuaternionType;
+
+    /// Same as `from_axis_angle`, but fails if `axis` has the wrong length or zero length
+    fn try_from_axis_angle(
+        axis: &[Self::ScalarType],
+        angle: Self::ScalarType,
+    ) -> Result<Self::QuaternionType, MathError> {
+        check_len(axis.len(), 3)?;
+        let len2 = axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2];
+        check_nonzero(len2.sqrt(), Self::ScalarType::EPSILON)?;
+        Ok(Self::from_axis_angle(axis, angle))
+    }
+
+    /// Same as `rotate_vector`, but fails if `v` has the wrong length
+    fn try_rotate_vector(
+        &self,
+        v: &[Self::ScalarType],
+    ) -> Result<Vec3<Self::ScalarType>, MathError> {
+        check_len(v.len(), 3)?;
+        Ok(self.rotate_vector(v))
+    }
+
+    /// Create a rotation from the given rotation matrix
+    #[cfg(feature = "Matrix3")]
+    fn from_mat3(m: &Mat3<Self::ScalarType>) -> Self::QuaternionType;
+
+    /// Convert this rotation to a rotation matrix
+    #[cfg(feature = "Matrix3")]
+    fn to_mat3(&self) -> Mat3<Self::ScalarType>;
+
+    /// Create a rotation from the upper 3x3 part of the given matrix
+    #[cfg(feature = "Matrix4")]
+    fn from_mat4(m: &Mat4<Self::ScalarType>) -> Self::QuaternionType;
+
+    /// Convert this rotation to a homogenous rotation matrix
+    #[cfg(feature = "Matrix4")]
+    fn to_mat4(&self) -> Mat4<Self::ScalarType>;
+}
+
+// Shoemake's conversion of a rotation matrix given as columns to a quaternion
+#[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
+fn from_columns<T: Scalar>(c: [[T; 3]; 3]) -> Quat<T> {
+    let half = T::from_f32(0.5);
+    let trace = c[0][0] + c[1][1] + c[2][2];
+    let mut out = [T::ZERO; 4];
+
+    if trace > T::ZERO {
+        let root = (trace + T::ONE).sqrt();
+        out[3] = half * root;
+        let root = half / root;
+        out[0] = (c[1][2] - c[2][1]) * root;
+        out[1] = (c[2][0] - c[0][2]) * root;
+        out[2] = (c[0][1] - c[1][0]) * root;
+    } else {
+        let mut i = 0;
+        if c[1][1] > c[0][0] {
+            i = 1;
+        }
+        if c[2][2] > c[i][i] {
+            i = 2;
+        }
+        let j = (i + 1) % 3;
+        let k = (i + 2) % 3;
+
+        let root = (c[i][i] - c[j][j] - c[k][k] + T::ONE).sqrt();
+        out[i] = half * root;
+        let root = half / root;
+        out[3] = (c[j][k] - c[k][j]) * root;
+        out[j] = (c[j][i] + c[i][j]) * root;
+        out[k] = (c[k][i] + c[i][k]) * root;
+    }
+
+    out
+}
+
+// The columns of the rotation matrix of the given unit quaternion
+#[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
+fn to_columns<T: Scalar>(q: &Quat<T>) -> [[T; 3]; 3] {
+    let [x, y, z, w] = *q;
+    let x2 = x + x;
+    let y2 = y + y;
+    let z2 = z + z;
+
+    let xx = x * x2;
+    let yx = y * x2;
+    let yy = y * y2;
+    let zx = z * x2;
+    let zy = z * y2;
+    let zz = z * z2;
+    let wx = w * x2;
+    let wy = w * y2;
+    let wz = w * z2;
+
+    [
+        [T::ONE - yy - zz, yx + wz, zx - wy],
+        [yx - wz, T::ONE - xx - zz, zy + wx],
+        [zx + wy, zy - wx, T::ONE - xx - yy],
+    ]
+}
+
+impl<T: Scalar> Quaternion for Quat<T> {
+    type QuaternionType = Quat<T>;
+    type ScalarType = T;
+
+    fn identity() -> Self {
+        [T::ZERO, T::ZERO, T::ZERO, T::ONE]
+    }
+
+    fn from_axis_angle(axis: &[T], angle: T) -> Self {
+        debug_assert!(axis.len() >= 3);
+
+        let len = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+
+        // no rotation around nothing
+        if len <= T::EPSILON {
+            debug_assert!(len > T::EPSILON);
+            return Self::identity();
+        }
+
+        let (s, c) = (angle * T::from_f32(0.5)).sin_cos();
+        let s = s / len;
+        [axis[0] * s, axis[1] * s, axis[2] * s, c]
+    }
+
+    fn mul_quat(&self, rhs: &Self) -> Self {
+        let [ax, ay, az, aw] = *self;
+        let [bx, by, bz, bw] = *rhs;
+
+        [
+            ax * bw + aw * bx + ay * bz - az * by,
+            ay * bw + aw * by + az * bx - ax * bz,
+            az * bw + aw * bz + ax * by - ay * bx,
+            aw * bw - ax * bx - ay * by - az * bz,
+        ]
+    }
+
+    fn conjugate(&self) -> Self {
+        [-self[0], -self[1], -self[2], self[3]]
+    }
+
+    fn normalize(&self) -> Self {
+        let len =
+            (self[0] * self[0] + self[1] * self[1] + self[2] * self[2] + self[3] * self[3]).sqrt();
+        if len <= T::EPSILON {
+            return Self::identity();
+        }
+
+        let inv = T::ONE / len;
+        [self[0] * inv, self[1] * inv, self[2] * inv, self[3] * inv]
+    }
+
+    fn rotate_vector(&self, v: &[T]) -> Vec3<T> {
+        debug_assert!(v.len() >= 3);
+
+        let [qx, qy, qz, qw] = *self;
+        let two = T::ONE + T::ONE;
+
+        // t = 2 * cross(q, v)
+        let tx = two * (qy * v[2] - qz * v[1]);
+        let ty = two * (qz * v[0] - qx * v[2]);
+        let tz = two * (qx * v[1] - qy * v[0]);
+
+        // v + w * t + cross(q, t)
+        [
+            v[0] + qw * tx + (qy * tz - qz * ty),
+            v[1] + qw * ty + (qz * tx - qx * tz),
+            v[2] + qw * tz + (qx * ty - qy * tx),
+        ]
+    }
+
+    fn slerp(&self, other: &Self, t: T) -> Self {
+        let mut cos =
+            self[0] * other[0] + self[1] * other[1] + self[2] * other[2] + self[3] * other[3];
+        let mut other = *other;
+        if cos < T::ZERO {
+            cos = -cos;
+            other = [-other[0], -other[1], -other[2], -other[3]];
+        }
+        // rounding may push the dot product of unit quaternions above 1
+        if cos > T::ONE {
+            cos = T::ONE;
+        }
+
+        let sin = (T::ONE - cos * cos).sqrt();
+        let (a, b) = if sin <= T::EPSILON {
+            // almost parallel, fall back to a linear interpolation
+            (T::ONE - t, t)
+        } else {
+            let angle = sin.atan2(cos);
+            let (sa, _) = ((T::ONE - t) * angle).sin_cos();
+            let (sb, _) = (t * angle).sin_cos();
+            (sa / sin, sb / sin)
+        };
+
+        [
+            a * self[0] + b * other[0],
+            a * self[1] + b * other[1],
+            a * self[2] + b * other[2],
+            a * self[3] + b * other[3],
+        ]
+        .normalize()
+    }
+
+    #[cfg(feature = "Matrix3")]
+    fn from_mat3(m: &Mat3<T>) -> Self {
+        from_columns([[m[0], m[1], m[2]], [m[3], m[4], m[5]], [m[6], m[7], m[8]]])
+    }
+
+    #[cfg(feature = "Matrix3")]
+    fn to_mat3(&self) -> Mat3<T> {
+        let c = to_columns(self);
+        [
+            c[0][0], c[0][1], c[0][2], c[1][0], c[1][1], c[1][2], c[2][0], c[2][1], c[2][2],
+        ]
+    }
+
+    #[cfg(feature = "Matrix4")]
+    fn from_mat4(m: &Mat4<T>) -> Self {
+        from_columns([[m[0], m[1], m[2]], [m[4], m[5], m[6]], [m[8], m[9], m[10]]])
+    }
+
+    #[cfg(feature = "Matrix4")]
+    fn to_mat4(&self) -> Mat4<T> {
+        let c = to_columns(self);
+        let o = T::ZERO;
+        [
+            c[0][0],
+            c[0][1],
+            c[0][2],
+            o,
+            c[1][0],
+            c[1][1],
+            c[1][2],
+            o,
+            c[2][0],
+            c[2][1],
+            c[2][2],
+            o,
+            o,
+            o,
+            o,
+            T::ONE,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq;
+    use std::f32;
+
+    #[test]
+    fn quat_identity() {
+        let q: Quat = Quat::identity();
+        assert_eq!(q, [0., 0., 0., 1.]);
+        assert_eq!(q.rotate_vector(&[1., 2., 3.]), [1., 2., 3.]);
+    }
+
+    #[test]
+    fn quat_rotate_vector() {
+        let q: Quat = Quat::from_axis_angle(&[0., 0., 2.], f32::consts::FRAC_PI_2);
+        assert!(almost_eq(&q.rotate_vector(&[-1., 3., 5.]), &[-3., -1., 5.]));
+    }
+
+    #[test]
+    fn quat_mul_quat() {
+        let a: Quat = Quat::from_axis_angle(&[1., 0., 0.], f32::consts::FRAC_PI_2);
+        let b: Quat = Quat::from_axis_angle(&[0., 1., 0.], f32::consts::FRAC_PI_2);
+
+        let v = [1., 2., 3.];
+        let ab = a.mul_quat(&b);
+        assert!(almost_eq(
+            &ab.rotate_vector(&v),
+            &a.rotate_vector(&b.rotate_vector(&v))
+        ));
+        assert!(almost_eq(&a.mul_quat(&a.conjugate()), &Quat::identity()));
+    }
+
+    #[test]
+    fn quat_normalize() {
+        let q: Quat = [0., 0., 3., 4.];
+        assert!(almost_eq(&q.normalize(), &[0., 0., 0.6, 0.8]));
+    }
+
+    #[test]
+    fn quat_slerp() {
+        let axis = [1., 2., -1.];
+        let a: Quat = Quat::from_axis_angle(&axis, 0.2);
+        let b: Quat = Quat::from_axis_angle(&axis, 1.4);
+
+        assert!(almost_eq(&a.slerp(&b, 0.), &a));
+        assert!(almost_eq(&a.slerp(&b, 1.), &b));
+        assert!(almost_eq(
+            &a.slerp(&b, 0.25),
+            &Quat::from_axis_angle(&axis, 0.5)
+        ));
+
+        // the other hemisphere is the same rotation
+        let flipped = [-b[0], -b[1], -b[2], -b[3]];
+        assert!(almost_eq(&a.slerp(&flipped, 0.25), &a.slerp(&b, 0.25)));
+        assert!(almost_eq(&a.slerp(&a, 0.5), &a));
+
+        // the dot product with itself is slightly above 1
+        let q: Quat = [0.0022075232, 0.0044371216, -0.00066225696, 0.9999876];
+        assert!(q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3] > 1.);
+        assert!(almost_eq(&q.slerp(&q, 0.5), &q));
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix4")]
+    fn quat_to_mat4_matches_rotate() {
+        use crate::matrix::Matrix;
+
+        let axis = [1., -2., 0.5];
+        let q: Quat = Quat::from_axis_angle(&axis, 0.7);
+
+        let mut m = Mat4::identity();
+        m.rotate(0.7, &axis);
+
+        assert!(almost_eq(&q.to_mat4(), &m));
+        assert!(almost_eq(&Quat::from_mat4(&m), &q));
+    }
+
+    #[test]
+    #[cfg(feature = "Matrix3")]
+    fn quat_mat3_round_trip() {
+        // exercise all branches of the matrix conversion
+        for &(axis, angle) in &[
+            ([1., 0., 0.], 3.),
+            ([0., 1., 0.], 3.),
+            ([0., 0., 1.], 3.),
+            ([1., 1., 1.], 0.5),
+        ] {
+            let q: Quat = Quat::from_axis_angle(&axis, angle);
+            let r = Quat::from_mat3(&q.to_mat3());
+            assert!(almost_eq(&r, &q));
+        }
+    }
+}
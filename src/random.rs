@@ -0,0 +1,192 @@
+//! Random directions, points in shapes and rotations for the `rand` crate.
+//!
+//! The types implement `Distribution` for the vector, quaternion and matrix types:
+//!
+//! ```rust
+//! use rand::Rng;
+//! use webgl_matrix::random::{CosineHemisphere, UnitSphere};
+//! use webgl_matrix::Vec3;
+//!
+//! # fn particles<R: Rng>(rng: &mut R) {
+//! let velocity: Vec3 = rng.sample(UnitSphere);
+//! let bounce: Vec3 = rng.sample(CosineHemisphere);
+//! # }
+//! ```
+//!
+//! With the `Quaternion` feature, `UniformRotation` samples a `Quat`, or a `Mat3` if
+//! `Matrix3` is enabled as well. The samples are computed from uniform `f32`s using the mappings of the `sampling` module.
+
+use crate::sampling;
+use crate::scalar::Scalar;
+use rand::distr::Distribution;
+use rand::Rng;
+
+#[cfg(feature = "Quaternion")]
+use crate::quat::Quat;
+#[cfg(all(feature = "Matrix3", feature = "Quaternion"))]
+use crate::{mat3::Mat3, matrix::RotationMatrix};
+
+fn uniform<T: Scalar, R: Rng + ?Sized, const N: usize>(rng: &mut R) -> [T; N] {
+    let mut u = [T::ZERO; N];
+    for x in &mut u {
+        *x = T::from_f32(rng.random::<f32>());
+    }
+    u
+}
+
+/// Directions on the unit sphere
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitSphere;
+
+impl<T: Scalar> Distribution<[T; 3]> for UnitSphere {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; 3] {
+        sampling::unit_sphere(uniform(rng))
+    }
+}
+
+/// Directions on the unit hemisphere around the positive Z-axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitHemisphere;
+
+impl<T: Scalar> Distribution<[T; 3]> for UnitHemisphere {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; 3] {
+        sampling::unit_hemisphere(uniform(rng))
+    }
+}
+
+/// Cosine-weighted directions on the unit hemisphere around the positive Z-axis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CosineHemisphere;
+
+impl<T: Scalar> Distribution<[T; 3]> for CosineHemisphere {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; 3] {
+        sampling::cosine_hemisphere(uniform(rng))
+    }
+}
+
+/// Points in the unit disc
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitDisc;
+
+impl<T: Scalar> Distribution<[T; 2]> for UnitDisc {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; 2] {
+        sampling::unit_disc(uniform(rng))
+    }
+}
+
+/// Points in the unit ball
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnitBall;
+
+impl<T: Scalar> Distribution<[T; 3]> for UnitBall {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; 3] {
+        sampling::unit_ball(uniform(rng))
+    }
+}
+
+/// Points in the triangle with the given corners
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle<T, const N: usize> {
+    pub a: [T; N],
+    pub b: [T; N],
+    pub c: [T; N],
+}
+
+impl<T: Scalar, const N: usize> Distribution<[T; N]> for Triangle<T, N> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; N] {
+        sampling::triangle(uniform(rng), &self.a, &self.b, &self.c)
+    }
+}
+
+/// Uniformly distributed rotations, as unit quaternions or rotation matrices
+#[cfg(feature = "Quaternion")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UniformRotation;
+
+#[cfg(feature = "Quaternion")]
+impl<T: Scalar> Distribution<Quat<T>> for UniformRotation {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quat<T> {
+        sampling::rotation(uniform(rng))
+    }
+}
+
+#[cfg(all(feature = "Matrix3", feature = "Quaternion"))]
+impl<T: Scalar> Distribution<Mat3<T>> for UniformRotation {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Mat3<T> {
+        let q: Quat<T> = self.sample(rng);
+        Mat3::from_quat(&q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_pcg::Pcg32;
+
+    fn rng() -> Pcg32 {
+        Pcg32::seed_from_u64(7)
+    }
+
+    fn mag(v: &[f32]) -> f32 {
+        v.iter().map(|x| x * x).sum::<f32>().sqrt()
+    }
+
+    #[test]
+    fn random_directions() {
+        let mut rng = rng();
+        let mut sum = [0.; 3];
+        for _ in 0..1000 {
+            let v: [f32; 3] = rng.sample(UnitSphere);
+            assert!((mag(&v) - 1.).abs() < 1e-5);
+            sum = [sum[0] + v[0], sum[1] + v[1], sum[2] + v[2]];
+
+            let h: [f32; 3] = rng.sample(UnitHemisphere);
+            assert!((mag(&h) - 1.).abs() < 1e-5 && h[2] >= 0.);
+
+            let c: [f64; 3] = rng.sample(CosineHemisphere);
+            assert!(c[2] >= 0.);
+        }
+        assert!(mag(&sum) / 1000. < 0.1);
+    }
+
+    #[test]
+    fn random_shapes() {
+        let mut rng = rng();
+        let triangle = Triangle {
+            a: [0., 0., 1.],
+            b: [1., 0., 1.],
+            c: [0., 1., 1.],
+        };
+        for _ in 0..1000 {
+            let d: [f32; 2] = rng.sample(UnitDisc);
+            assert!(mag(&d) <= 1.);
+
+            let b: [f32; 3] = rng.sample(UnitBall);
+            assert!(mag(&b) <= 1.);
+
+            let p = rng.sample(triangle);
+            assert!(
+                p[0] >= 0. && p[1] >= 0. && p[0] + p[1] <= 1. + 1e-6 && (p[2] - 1.).abs() < 1e-6
+            );
+        }
+    }
+
+    #[cfg(feature = "Quaternion")]
+    #[test]
+    fn random_rotations() {
+        let mut rng = rng();
+        let q: Quat = rng.sample(UniformRotation);
+        assert!((mag(&q) - 1.).abs() < 1e-5);
+
+        #[cfg(feature = "Matrix3")]
+        {
+            use crate::orthonormal::OrthonormalMatrix;
+
+            for _ in 0..100 {
+                let m: Mat3 = rng.sample(UniformRotation);
+                assert!(m.is_rotation());
+            }
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Low-discrepancy sequences and mappings of the unit square onto shapes.
+//!
+//! The mappings turn uniformly distributed points in `[0, 1)^n` into uniformly distributed
+//! points in the respective shape. Combined with the Halton or Hammersley sequence they yield
+//! evenly spread samples without a random number generator, e.g. for light baking:
+//!
+//! ```rust
+//! use webgl_matrix::sampling;
+//!
+//! let count = 64;
+//! let directions: Vec<[f32; 3]> = (0..count)
+//!     .map(|i| sampling::cosine_hemisphere(sampling::hammersley(i, count)))
+//!     .collect();
+//! assert!(directions.iter().all(|d| d[2] >= 0.));
+//! ```
+//!
+//! See the `random` module (feature `rand`) for random samples of the same shapes.
+
+use crate::scalar::Scalar;
+use crate::utils::pi;
+
+#[cfg(feature = "Quaternion")]
+use crate::quat::Quat;
+
+/// The radical inverse of `index` in the given base, i.e. its digits mirrored at the
+/// decimal point
+///
+/// This is the van der Corput sequence for the given base, the result is in `[0, 1)`.
+/// Panics if `base` is less than 2.
+pub fn radical_inverse<T: Scalar>(index: u32, base: u32) -> T {
+    assert!(
+        base >= 2,
+        "the base of the radical inverse has to be at least 2"
+    );
+
+    let b = T::from_f32(base as f32);
+    let mut scale = T::ONE / b;
+    let mut out = T::ZERO;
+    let mut i = index;
+    while i > 0 {
+        out += T::from_f32((i % base) as f32) * scale;
+        scale /= b;
+        i /= base;
+    }
+
+    // large indices round up to 1 in `f32`
+    let below_one = T::from_f32(1. - f32::EPSILON / 2.);
+    if out < below_one {
+        out
+    } else {
+        below_one
+    }
+}
+
+/// The element of the Halton sequence with the given index in the given (prime) base
+///
+/// The first element (index 0) is always 0, so it is commonly skipped. Panics if `base` is
+/// less than 2.
+pub fn halton<T: Scalar>(index: u32, base: u32) -> T {
+    radical_inverse(index, base)
+}
+
+/// The element of the 2D Halton sequence with the bases 2 and 3
+pub fn halton_2d<T: Scalar>(index: u32) -> [T; 2] {
+    [halton(index, 2), halton(index, 3)]
+}
+
+/// The element of the 3D Halton sequence with the bases 2, 3 and 5
+pub fn halton_3d<T: Scalar>(index: u32) -> [T; 3] {
+    [halton(index, 2), halton(index, 3), halton(index, 5)]
+}
+
+/// The point with the given index of the Hammersley set with `count` points
+///
+/// Unlike the Halton sequence, the number of points has to be known in advance, but the
+/// points are spread more evenly. Panics if `count` is 0.
+pub fn hammersley<T: Scalar>(index: u32, count: u32) -> [T; 2] {
+    assert!(
+        count > 0,
+        "the Hammersley set has to contain at least one point"
+    );
+    [
+        T::from_f32(index as f32) / T::from_f32(count as f32),
+        radical_inverse(index, 2),
+    ]
+}
+
+// `2π` times the given fraction of a turn as cosine and sine
+fn turn<T: Scalar>(fraction: T) -> (T, T) {
+    let pi = pi::<T>();
+    let (sin, cos) = ((pi + pi) * fraction).sin_cos();
+    (cos, sin)
+}
+
+fn sqrt_clamped<T: Scalar>(x: T) -> T {
+    if x > T::ZERO {
+        x.sqrt()
+    } else {
+        T::ZERO
+    }
+}
+
+/// Map the given point of the unit square to a direction on the unit sphere
+pub fn unit_sphere<T: Scalar>(u: [T; 2]) -> [T; 3] {
+    let z = T::ONE - (u[0] + u[0]);
+    let r = sqrt_clamped(T::ONE - z * z);
+    let (cos, sin) = turn(u[1]);
+    [r * cos, r * sin, z]
+}
+
+/// Map the given point of the unit square to a direction on the unit hemisphere around the
+/// positive Z-axis, e.g. the normal in tangent space
+pub fn unit_hemisphere<T: Scalar>(u: [T; 2]) -> [T; 3] {
+    let z = T::ONE - u[0];
+    let r = sqrt_clamped(T::ONE - z * z);
+    let (cos, sin) = turn(u[1]);
+    [r * cos, r * sin, z]
+}
+
+/// Map the given point of the unit square to a direction on the unit hemisphere around the
+/// positive Z-axis, with a density proportional to the cosine of the angle to the Z-axis
+///
+/// This is the importance sampling of diffuse (Lambertian) reflections.
+pub fn cosine_hemisphere<T: Scalar>(u: [T; 2]) -> [T; 3] {
+    let [x, y] = unit_disc(u);
+    [x, y, sqrt_clamped(T::ONE - x * x - y * y)]
+}
+
+/// Map the given point of the unit square to a point in the unit disc
+///
+/// This is the concentric mapping of Shirley and Chiu, which keeps neighbouring points
+/// close to each other, i.e. the distribution of low-discrepancy points is preserved.
+pub fn unit_disc<T: Scalar>(u: [T; 2]) -> [T; 2] {
+    let a = u[0] + u[0] - T::ONE;
+    let b = u[1] + u[1] - T::ONE;
+    if a == T::ZERO && b == T::ZERO {
+        return [T::ZERO; 2];
+    }
+
+    let eighth = T::from_f32(0.125);
+    let (r, fraction) = if a.abs() > b.abs() {
+        (a, eighth * b / a)
+    } else {
+        (b, eighth + eighth - eighth * a / b)
+    };
+    let (cos, sin) = turn(fraction);
+    [r * cos, r * sin]
+}
+
+/// Map the given point of the unit cube to a point in the unit ball
+pub fn unit_ball<T: Scalar>(u: [T; 3]) -> [T; 3] {
+    let [x, y, z] = unit_sphere([u[0], u[1]]);
+    let r = u[2].powf(T::ONE / T::from_f32(3.));
+    [x * r, y * r, z * r]
+}
+
+/// Map the given point of the unit square to a point in the triangle `abc`
+pub fn triangle<T: Scalar, const N: usize>(
+    u: [T; 2],
+    a: &[T; N],
+    b: &[T; N],
+    c: &[T; N],
+) -> [T; N] {
+    let s = u[0].sqrt();
+    let (wa, wb, wc) = (T::ONE - s, s * (T::ONE - u[1]), s * u[1]);
+
+    let mut out = [T::ZERO; N];
+    for (i, o) in out.iter_mut().enumerate() {
+        *o = a[i] * wa + b[i] * wb + c[i] * wc;
+    }
+    out
+}
+
+/// Map the given point of the unit cube to a uniformly distributed rotation
+///
+/// This is the method of Shoemake, the result is a unit quaternion.
+#[cfg(feature = "Quaternion")]
+pub fn rotation<T: Scalar>(u: [T; 3]) -> Quat<T> {
+    let r1 = sqrt_clamped(T::ONE - u[0]);
+    let r2 = sqrt_clamped(u[0]);
+    let (cos1, sin1) = turn(u[1]);
+    let (cos2, sin2) = turn(u[2]);
+    [r1 * sin1, r1 * cos1, r2 * sin2, r2 * cos2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{almost_eq, almost_eq_relative};
+
+    fn mag(v: &[f64]) -> f64 {
+        v.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    // the mean of the given mapping over a Hammersley set
+    fn mean(count: u32, map: impl Fn([f64; 2]) -> [f64; 3]) -> [f64; 3] {
+        let mut sum = [0.; 3];
+        for i in 0..count {
+            let p = map(hammersley(i, count));
+            for k in 0..3 {
+                sum[k] += p[k] / count as f64;
+            }
+        }
+        sum
+    }
+
+    #[test]
+    fn sampling_sequences() {
+        let base2: Vec<f32> = (0..5).map(|i| halton(i, 2)).collect();
+        assert_eq!(base2, vec![0., 0.5, 0.25, 0.75, 0.125]);
+        assert!(almost_eq(
+            &[halton::<f64>(1, 3), halton(5, 3)],
+            &[1. / 3., 7. / 9.]
+        ));
+        assert_eq!(halton_2d::<f32>(3), [0.75, halton(3, 3)]);
+        assert_eq!(halton_3d::<f32>(1), [0.5, halton(1, 3), 0.2]);
+
+        assert_eq!(hammersley::<f32>(3, 4), [0.75, 0.75]);
+        assert_eq!(hammersley::<f32>(0, 4), [0., 0.]);
+        assert!(radical_inverse::<f64>(u32::MAX, 2) < 1.);
+        assert!(radical_inverse::<f32>(u32::MAX, 2) < 1.);
+        assert!(radical_inverse::<f32>(u32::MAX, 3) < 1.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sampling_invalid_base() {
+        radical_inverse::<f32>(5, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sampling_empty_hammersley() {
+        hammersley::<f32>(0, 0);
+    }
+
+    #[test]
+    fn sampling_directions() {
+        for i in 0..100 {
+            let u = halton_2d::<f64>(i);
+            assert!((mag(&unit_sphere(u)) - 1.).abs() < 1e-10);
+
+            let h = unit_hemisphere(u);
+            assert!((mag(&h) - 1.).abs() < 1e-10 && h[2] >= 0.);
+
+            let c = cosine_hemisphere(u);
+            assert!((mag(&c) - 1.).abs() < 1e-10 && c[2] >= 0.);
+        }
+
+        // the mean of `z` is 0 for the sphere, 1/2 for the hemisphere and 2/3 for the
+        // cosine-weighted hemisphere
+        let tol = |a: &[f64; 3], b: &[f64; 3]| almost_eq_relative(a, b, 1e-2, 0.);
+        assert!(tol(&mean(1024, unit_sphere), &[0., 0., 0.]));
+        assert!(tol(&mean(1024, unit_hemisphere), &[0., 0., 0.5]));
+        assert!(tol(&mean(1024, cosine_hemisphere), &[0., 0., 2. / 3.]));
+    }
+
+    #[test]
+    fn sampling_shapes() {
+        assert_eq!(unit_disc::<f32>([0.5, 0.5]), [0., 0.]);
+        assert!(almost_eq(&unit_disc::<f32>([1., 0.5]), &[1., 0.]));
+        assert!(almost_eq(&unit_disc::<f32>([0.5, 1.]), &[0., 1.]));
+
+        let a = [1., 0.];
+        let b = [3., 0.];
+        let c = [1., 2.];
+        for i in 0..100 {
+            let [x, y, z] = halton_3d::<f64>(i);
+            assert!(mag(&unit_disc([x, y])) <= 1.);
+            assert!(mag(&unit_ball([x, y, z])) <= 1.);
+
+            // inside the triangle, i.e. above `ab`, right of `ac` and below `bc`
+            let p = triangle([x, y], &a, &b, &c);
+            assert!(p[1] >= 0. && p[0] >= 1. && p[0] + p[1] <= 3. + 1e-10);
+        }
+
+        // the mean is the centroid
+        let mut sum = [0.; 2];
+        for i in 0..1024 {
+            let p = triangle(hammersley(i, 1024), &a, &b, &c);
+            sum = [sum[0] + p[0] / 1024., sum[1] + p[1] / 1024.];
+        }
+        assert!(almost_eq_relative(&sum, &[5. / 3., 2. / 3.], 1e-2, 0.));
+    }
+
+    #[cfg(feature = "Quaternion")]
+    #[test]
+    fn sampling_rotation() {
+        use crate::quat::Quaternion;
+
+        let mut sum = [0.; 3];
+        for i in 1..=1000 {
+            let q = rotation::<f64>(halton_3d(i));
+            assert!((mag(&q) - 1.).abs() < 1e-10);
+
+            // uniform rotations of a vector are uniform on the sphere
+            let v = q.rotate_vector(&[0., 0., 1.]);
+            sum = [sum[0] + v[0], sum[1] + v[1], sum[2] + v[2]];
+        }
+        assert!(mag(&sum) / 1000. < 2e-2);
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// The element type of all vectors and matrices
+///
+/// Implemented for `f32` and `f64`. Other number types, e.g. a fixed-point type
+/// for deterministic simulations, can be used by implementing this trait.
+pub trait Scalar:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    /// The additive identity
+    const ZERO: Self;
+    /// The multiplicative identity
+    const ONE: Self;
+    /// The tolerance used for approximate comparisons, e.g. when checking for singular matrices
+    const EPSILON: Self;
+
+    /// Converts the given `f32` constant into this type
+    fn from_f32(value: f32) -> Self;
+    /// Converts this value into a `f32`, possibly losing precision
+    fn to_f32(self) -> f32;
+
+    /// Compute the absolute value
+    fn abs(self) -> Self;
+    /// Compute the square root
+    fn sqrt(self) -> Self;
+    /// Compute the sine and cosine (radians) at once
+    fn sin_cos(self) -> (Self, Self);
+    /// Compute the tangent (radians)
+    fn tan(self) -> Self;
+    /// Compute the arccosine in radians
+    fn acos(self) -> Self;
+    /// Compute the four quadrant arctangent of `self` (y) and `other` (x) in radians
+    fn atan2(self, other: Self) -> Self;
+
+    /// Compute the largest integer less than or equal to `self`
+    ///
+    /// The default implementation goes through `f32`.
+    fn floor(self) -> Self {
+        Self::from_f32(self.to_f32().floor())
+    }
+    /// Raise `self` to the given floating point power
+    ///
+    /// The default implementation goes through `f32`.
+    fn powf(self, n: Self) -> Self {
+        Self::from_f32(self.to_f32().powf(n.to_f32()))
+    }
+}
+
+macro_rules! impl_scalar {
+    ($type:ident, $epsilon:expr) => {
+        impl Scalar for $type {
+            const ZERO: $type = 0.;
+            const ONE: $type = 1.;
+            const EPSILON: $type = $epsilon;
+
+            #[inline]
+            fn from_f32(value: f32) -> $type {
+                value as $type
+            }
+            #[inline]
+            fn to_f32(self) -> f32 {
+                self as f32
+            }
+
+            #[inline]
+            fn abs(self) -> $type {
+                $type::abs(self)
+            }
+            #[inline]
+            fn sqrt(self) -> $type {
+                $type::sqrt(self)
+            }
+            #[inline]
+            fn sin_cos(self) -> ($type, $type) {
+                $type::sin_cos(self)
+            }
+            #[inline]
+            fn tan(self) -> $type {
+                $type::tan(self)
+            }
+            #[inline]
+            fn acos(self) -> $type {
+                $type::acos(self)
+            }
+            #[inline]
+            fn atan2(self, other: $type) -> $type {
+                $type::atan2(self, other)
+            }
+            #[inline]
+            fn floor(self) -> $type {
+                $type::floor(self)
+            }
+            #[inline]
+            fn powf(self, n: $type) -> $type {
+                $type::powf(self, n)
+            }
+        }
+    };
+}
+
+// we do not use the machine epsilons here, they are too strict for accumulated errors
+impl_scalar!(f32, 1e-5);
+impl_scalar!(f64, 1e-10);
+
+#[cfg(all(test, feature = "Matrix3"))]
+mod tests {
+    use super::*;
+    use crate::{utils::almost_eq, Mat3, Matrix};
+    use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+    /// A minimal 16.16 fixed-point number
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Fixed(i64);
+
+    const FRAC: i64 = 1 << 16;
+
+    impl Add for Fixed {
+        type Output = Fixed;
+        fn add(self, rhs: Fixed) -> Fixed {
+            Fixed(self.0 + rhs.0)
+        }
+    }
+    impl Sub for Fixed {
+        type Output = Fixed;
+        fn sub(self, rhs: Fixed) -> Fixed {
+            Fixed(self.0 - rhs.0)
+        }
+    }
+    impl Mul for Fixed {
+        type Output = Fixed;
+        fn mul(self, rhs: Fixed) -> Fixed {
+            Fixed(self.0 * rhs.0 / FRAC)
+        }
+    }
+    impl Div for Fixed {
+        type Output = Fixed;
+        fn div(self, rhs: Fixed) -> Fixed {
+            Fixed(self.0 * FRAC / rhs.0)
+        }
+    }
+    impl Neg for Fixed {
+        type Output = Fixed;
+        fn neg(self) -> Fixed {
+            Fixed(-self.0)
+        }
+    }
+    impl AddAssign for Fixed {
+        fn add_assign(&mut self, rhs: Fixed) {
+            *self = *self + rhs;
+        }
+    }
+    impl SubAssign for Fixed {
+        fn sub_assign(&mut self, rhs: Fixed) {
+            *self = *self - rhs;
+        }
+    }
+    impl MulAssign for Fixed {
+        fn mul_assign(&mut self, rhs: Fixed) {
+            *self = *self * rhs;
+        }
+    }
+    impl DivAssign for Fixed {
+        fn div_assign(&mut self, rhs: Fixed) {
+            *self = *self / rhs;
+        }
+    }
+
+    impl Scalar for Fixed {
+        const ZERO: Fixed = Fixed(0);
+        const ONE: Fixed = Fixed(FRAC);
+        const EPSILON: Fixed = Fixed(16);
+
+        fn from_f32(value: f32) -> Fixed {
+            Fixed((value * FRAC as f32) as i64)
+        }
+        fn to_f32(self) -> f32 {
+            self.0 as f32 / FRAC as f32
+        }
+        fn abs(self) -> Fixed {
+            Fixed(self.0.abs())
+        }
+        fn sqrt(self) -> Fixed {
+            Fixed::from_f32(self.to_f32().sqrt())
+        }
+        fn sin_cos(self) -> (Fixed, Fixed) {
+            let (s, c) = self.to_f32().sin_cos();
+            (Fixed::from_f32(s), Fixed::from_f32(c))
+        }
+        fn tan(self) -> Fixed {
+            Fixed::from_f32(self.to_f32().tan())
+        }
+        fn acos(self) -> Fixed {
+            Fixed::from_f32(self.to_f32().acos())
+        }
+        fn atan2(self, other: Fixed) -> Fixed {
+            Fixed::from_f32(self.to_f32().atan2(other.to_f32()))
+        }
+    }
+
+    #[test]
+    fn scalar_f64_matrix() {
+        let mut a: [f64; 9] = [1., 3., 2., 4., 2., 8., 9., 2., 7.];
+        let b = a;
+
+        a.inverse().expect("Inverse should exist");
+        assert!(almost_eq(a.mul(&b), &<[f64; 9]>::identity()));
+    }
+
+    #[test]
+    fn scalar_custom_type() {
+        let mut a = Mat3::<Fixed>::identity();
+        a[0] = Fixed::from_f32(2.);
+        a[4] = Fixed::from_f32(4.);
+        a[8] = Fixed::from_f32(0.5);
+
+        assert_eq!(a.det(), Fixed::from_f32(4.));
+
+        a.inverse().expect("Inverse should exist");
+        assert_eq!(a[0], Fixed::from_f32(0.5));
+        assert_eq!(a[4], Fixed::from_f32(0.25));
+        assert_eq!(a[8], Fixed::from_f32(2.));
+        assert_eq!(a[1], Fixed::ZERO);
+    }
+}
@@ -0,0 +1,294 @@
+//! Helpers for (de)serializing the plain array types in structured forms.
+//!
+//! Since all types are plain arrays, they (de)serialize as flat sequences out of the box.
+//! The modules in here are meant to be used with serde's `with` attribute to choose
+//! a more descriptive form:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use webgl_matrix::{Mat3, Mat4};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Node {
+//!     #[serde(with = "webgl_matrix::serde_helpers::mat4_columns")]
+//!     projection: Mat4,
+//!     #[serde(with = "webgl_matrix::serde_helpers::mat3_columns")]
+//!     normal: Mat3,
+//!     #[serde(deserialize_with = "webgl_matrix::serde_helpers::deserialize_chunks::<_, 16>")]
+//!     instances: Vec<f32>,
+//! }
+//! ```
+//!
+//! With the `Quaternion` feature, `quat` (de)serializes a `Quat` as `{ x, y, z, w }` and
+//! `mat4_trs` an affine `Mat4` as `{ translation, rotation, scale }`.
+
+use serde::de::{Deserializer, Error};
+use serde::Deserialize;
+
+/// Deserializes a flat sequence and validates that its length is a multiple of `N`
+///
+/// This is useful to check buffers of e.g. `Mat4`s (`N = 16`), which are later used with
+/// the slice based APIs.
+pub fn deserialize_chunks<'de, D, const N: usize>(deserializer: D) -> Result<Vec<f32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Vec::<f32>::deserialize(deserializer)?;
+    if N == 0 || values.len() % N != 0 {
+        return Err(D::Error::invalid_length(
+            values.len(),
+            &format!("a multiple of {}", N).as_str(),
+        ));
+    }
+
+    Ok(values)
+}
+
+/// Deserializes a flat sequence and validates that its length is exactly `N`
+pub fn deserialize_exact<'de, D, const N: usize>(deserializer: D) -> Result<Vec<f32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Vec::<f32>::deserialize(deserializer)?;
+    if values.len() != N {
+        return Err(D::Error::invalid_length(
+            values.len(),
+            &format!("exactly {}", N).as_str(),
+        ));
+    }
+
+    Ok(values)
+}
+
+/// (De)serializes a `Quat` as `{ x, y, z, w }`
+#[cfg(feature = "Quaternion")]
+pub mod quat {
+    use crate::quat::Quat;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct QuatForm<T> {
+        x: T,
+        y: T,
+        z: T,
+        w: T,
+    }
+
+    pub fn serialize<T, S>(q: &Quat<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        QuatForm {
+            x: &q[0],
+            y: &q[1],
+            z: &q[2],
+            w: &q[3],
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Quat<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let q = QuatForm::deserialize(deserializer)?;
+        Ok([q.x, q.y, q.z, q.w])
+    }
+}
+
+/// (De)serializes a `Mat4` as a sequence of its four columns
+#[cfg(feature = "Matrix4")]
+pub mod mat4_columns {
+    use crate::mat4::Mat4;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(m: &Mat4<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + Copy,
+        S: Serializer,
+    {
+        [
+            [m[0], m[1], m[2], m[3]],
+            [m[4], m[5], m[6], m[7]],
+            [m[8], m[9], m[10], m[11]],
+            [m[12], m[13], m[14], m[15]],
+        ]
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Mat4<T>, D::Error>
+    where
+        T: Deserialize<'de> + Copy,
+        D: Deserializer<'de>,
+    {
+        let [c0, c1, c2, c3] = <[[T; 4]; 4]>::deserialize(deserializer)?;
+        Ok([
+            c0[0], c0[1], c0[2], c0[3], c1[0], c1[1], c1[2], c1[3], c2[0], c2[1], c2[2], c2[3],
+            c3[0], c3[1], c3[2], c3[3],
+        ])
+    }
+}
+
+/// (De)serializes a `Mat3` as a sequence of its three columns
+#[cfg(feature = "Matrix3")]
+pub mod mat3_columns {
+    use crate::mat3::Mat3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(m: &Mat3<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + Copy,
+        S: Serializer,
+    {
+        [[m[0], m[1], m[2]], [m[3], m[4], m[5]], [m[6], m[7], m[8]]].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Mat3<T>, D::Error>
+    where
+        T: Deserialize<'de> + Copy,
+        D: Deserializer<'de>,
+    {
+        let [c0, c1, c2] = <[[T; 3]; 3]>::deserialize(deserializer)?;
+        Ok([
+            c0[0], c0[1], c0[2], c1[0], c1[1], c1[2], c2[0], c2[1], c2[2],
+        ])
+    }
+}
+
+/// (De)serializes an affine `Mat4` in its decomposed form `{ translation, rotation, scale }`
+///
+/// See `Trs::from_mat4` for the limitations of the decomposition.
+#[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+pub mod mat4_trs {
+    use crate::mat4::Mat4;
+    use crate::scalar::Scalar;
+    use crate::trs::Trs;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T, S>(m: &Mat4<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Scalar + Serialize,
+        S: Serializer,
+    {
+        Trs::from_mat4(m).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Mat4<T>, D::Error>
+    where
+        T: Scalar + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Trs::deserialize(deserializer)?.to_mat4())
+    }
+}
+
+#[cfg(all(test, feature = "Matrix3", feature = "Matrix4", feature = "Quaternion"))]
+mod tests {
+    use super::*;
+    use crate::quat::{Quat, Quaternion};
+    use crate::trs::Trs;
+    use crate::utils::almost_eq;
+    use crate::{Mat3, Mat4, Matrix};
+    use serde::Serialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Scene {
+        #[serde(with = "mat4_trs")]
+        transform: Mat4,
+        #[serde(with = "mat4_columns")]
+        projection: Mat4,
+        #[serde(with = "mat3_columns")]
+        normal: Mat3,
+        #[serde(with = "quat")]
+        orientation: Quat,
+        #[serde(deserialize_with = "deserialize_chunks::<_, 4>")]
+        colors: Vec<f32>,
+    }
+
+    fn scene() -> Scene {
+        Scene {
+            transform: Trs::new(
+                [1., 2., 3.],
+                Quat::from_axis_angle(&[0., 1., 0.], 1.),
+                [2., 2., 2.],
+            )
+            .to_mat4(),
+            projection: [
+                1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 13., 14., 15., 16.,
+            ],
+            normal: Mat3::identity(),
+            orientation: [0., 0., 0., 1.],
+            colors: vec![1., 0., 0., 1., 0., 1., 0., 1.],
+        }
+    }
+
+    fn assert_scene_eq(a: &Scene, b: &Scene) {
+        assert!(almost_eq(&a.transform, &b.transform));
+        assert_eq!(a.projection, b.projection);
+        assert_eq!(a.normal, b.normal);
+        assert_eq!(a.orientation, b.orientation);
+        assert_eq!(a.colors, b.colors);
+    }
+
+    #[test]
+    fn serde_json_round_trip() {
+        let scene = scene();
+        let json = serde_json::to_string(&scene).unwrap();
+        let parsed: Scene = serde_json::from_str(&json).unwrap();
+        assert_scene_eq(&scene, &parsed);
+    }
+
+    #[test]
+    fn serde_ron_round_trip() {
+        let scene = scene();
+        let ron = ron::to_string(&scene).unwrap();
+        let parsed: Scene = ron::from_str(&ron).unwrap();
+        assert_scene_eq(&scene, &parsed);
+    }
+
+    #[test]
+    fn serde_structured_forms() {
+        let value = serde_json::to_value(scene()).unwrap();
+
+        assert_eq!(
+            value["orientation"],
+            serde_json::json!({"x": 0., "y": 0., "z": 0., "w": 1.})
+        );
+        assert_eq!(
+            value["projection"][3],
+            serde_json::json!([13., 14., 15., 16.])
+        );
+        assert_eq!(value["normal"][1], serde_json::json!([0., 1., 0.]));
+        assert!(almost_eq(
+            &[value["transform"]["translation"][1].as_f64().unwrap()],
+            &[2.]
+        ));
+        assert!(almost_eq(
+            &[value["transform"]["scale"][0].as_f64().unwrap()],
+            &[2.]
+        ));
+    }
+
+    #[test]
+    fn serde_chunks_validates_length() {
+        let mut value = serde_json::to_value(scene()).unwrap();
+        value["colors"] = serde_json::json!([1., 0., 0.]);
+
+        assert!(serde_json::from_value::<Scene>(value).is_err());
+    }
+
+    #[test]
+    fn serde_exact_validates_length() {
+        #[derive(Deserialize)]
+        struct Uniform {
+            #[serde(deserialize_with = "deserialize_exact::<_, 3>")]
+            #[allow(dead_code)]
+            value: Vec<f32>,
+        }
+
+        assert!(serde_json::from_str::<Uniform>(r#"{"value": [1, 2, 3]}"#).is_ok());
+        assert!(serde_json::from_str::<Uniform>(r#"{"value": [1, 2]}"#).is_err());
+    }
+}
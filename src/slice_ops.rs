@@ -1,11 +1,12 @@
-use std::f32;
+use crate::error::{check_len, MathError};
+use crate::scalar::Scalar;
 
 #[inline]
 /// Performs element-wise multiplication and places the result into `lhs`
 ///
 /// Terminates at the end of the shorter sequence.
-pub fn mul(lhs: &mut [f32], rhs: &[f32]) {
-    for (l, r) in lhs.iter_mut().zip(rhs.iter()) {
+pub fn mul<T: Scalar>(lhs: &mut [T], rhs: &[T]) {
+    for (l, &r) in lhs.iter_mut().zip(rhs.iter()) {
         *l *= r;
     }
 }
@@ -14,8 +15,8 @@ pub fn mul(lhs: &mut [f32], rhs: &[f32]) {
 /// Performs element-wise addition and places the result into `lhs`
 ///
 /// Terminates at the end of the shorter sequence.
-pub fn add(lhs: &mut [f32], rhs: &[f32]) {
-    for (l, r) in lhs.iter_mut().zip(rhs.iter()) {
+pub fn add<T: Scalar>(lhs: &mut [T], rhs: &[T]) {
+    for (l, &r) in lhs.iter_mut().zip(rhs.iter()) {
         *l += r;
     }
 }
@@ -24,15 +25,15 @@ pub fn add(lhs: &mut [f32], rhs: &[f32]) {
 /// Performs element-wise substraction and places the result into `lhs`
 ///
 /// Terminates at the end of the shorter sequence.
-pub fn sub(lhs: &mut [f32], rhs: &[f32]) {
-    for (l, r) in lhs.iter_mut().zip(rhs.iter()) {
+pub fn sub<T: Scalar>(lhs: &mut [T], rhs: &[T]) {
+    for (l, &r) in lhs.iter_mut().zip(rhs.iter()) {
         *l -= r;
     }
 }
 
 #[inline]
 /// Multiplies the given sequence element-wise with the given constant factor
-pub fn scale(seq: &mut [f32], factor: f32) {
+pub fn scale<T: Scalar>(seq: &mut [T], factor: T) {
     for i in seq.iter_mut() {
         *i *= factor;
     }
@@ -40,28 +41,69 @@ pub fn scale(seq: &mut [f32], factor: f32) {
 
 #[inline]
 /// Calculates the magnitude of the given sequence, same as sqrt(`mag2(seq)`)
-pub fn mag(seq: &[f32]) -> f32 {
+pub fn mag<T: Scalar>(seq: &[T]) -> T {
     mag2(seq).sqrt()
 }
 
 #[inline]
 /// Calculates the squared magnitude of the given sequence, i.e. `seq[0] * seq[0] + seq[1] * seq[1] + ...`
-pub fn mag2(seq: &[f32]) -> f32 {
-    let mut sum = 0.0;
-    for i in seq.iter() {
+pub fn mag2<T: Scalar>(seq: &[T]) -> T {
+    let mut sum = T::ZERO;
+    for &i in seq.iter() {
         sum += i * i;
     }
     sum
 }
 
+#[inline]
+/// Linearly interpolates between `lhs` (`t = 0`) and `rhs` (`t = 1`) and places the result into `lhs`
+///
+/// Same as GLSL `mix`. Terminates at the end of the shorter sequence.
+pub fn lerp<T: Scalar>(lhs: &mut [T], rhs: &[T], t: T) {
+    for (l, &r) in lhs.iter_mut().zip(rhs.iter()) {
+        *l = *l * (T::ONE - t) + r * t;
+    }
+}
+
 #[inline]
 /// Calculates the standard dot product of the two sequences.
 ///
 /// Terminates at the end of the shorter sequence.
-pub fn dot(lhs: &[f32], rhs: &[f32]) -> f32 {
-    let mut sum = 0.0;
-    for (i1, i2) in lhs.iter().zip(rhs.iter()) {
+pub fn dot<T: Scalar>(lhs: &[T], rhs: &[T]) -> T {
+    let mut sum = T::ZERO;
+    for (&i1, &i2) in lhs.iter().zip(rhs.iter()) {
         sum += i1 * i2;
     }
     sum
 }
+
+#[inline]
+/// Same as `mul`, but fails if the sequences have different lengths
+pub fn try_mul<T: Scalar>(lhs: &mut [T], rhs: &[T]) -> Result<(), MathError> {
+    check_len(rhs.len(), lhs.len())?;
+    mul(lhs, rhs);
+    Ok(())
+}
+
+#[inline]
+/// Same as `add`, but fails if the sequences have different lengths
+pub fn try_add<T: Scalar>(lhs: &mut [T], rhs: &[T]) -> Result<(), MathError> {
+    check_len(rhs.len(), lhs.len())?;
+    add(lhs, rhs);
+    Ok(())
+}
+
+#[inline]
+/// Same as `sub`, but fails if the sequences have different lengths
+pub fn try_sub<T: Scalar>(lhs: &mut [T], rhs: &[T]) -> Result<(), MathError> {
+    check_len(rhs.len(), lhs.len())?;
+    sub(lhs, rhs);
+    Ok(())
+}
+
+#[inline]
+/// Same as `dot`, but fails if the sequences have different lengths
+pub fn try_dot<T: Scalar>(lhs: &[T], rhs: &[T]) -> Result<T, MathError> {
+    check_len(rhs.len(), lhs.len())?;
+    Ok(dot(lhs, rhs))
+}
@@ -0,0 +1,537 @@
+//! Cubic spline curves, e.g. for camera rails, particle paths or animation curves.
+//!
+//! Splines work with any of the vector types (`Vec2`, `Vec3`, `Vec4`) or plain arrays.
+//! The curve parameter `t` runs from `0` at the start to `1` at the end of the whole spline:
+//!
+//! ```rust
+//! use webgl_matrix::spline::{Spline, SplineKind};
+//!
+//! let spline = Spline::new(SplineKind::CatmullRom, vec![[0., 0.], [1., 1.], [2., 0.]]).unwrap();
+//! assert_eq!(spline.position(0.5), [1., 1.]);
+//!
+//! // 16 evenly spaced points along the curve, e.g. for a `LINE_STRIP`
+//! let table = spline.arc_length_table(64);
+//! let vertices: Vec<f32> = spline.resample(&table, 16);
+//! assert_eq!(vertices.len(), 16 * 2);
+//! ```
+
+use crate::error::{check_len, MathError};
+use crate::scalar::Scalar;
+
+/// The type of a spline, which determines how its points are interpreted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplineKind {
+    /// Piecewise cubic Bézier curve
+    ///
+    /// The points are `[p0, c0, c1, p1, c2, c3, p2, ..]`, the curve passes through every third
+    /// point and the two points in between are the control points.
+    Bezier,
+    /// Catmull-Rom spline, which passes through all points
+    ///
+    /// The missing neighbours of the first and last point are mirrored.
+    CatmullRom,
+    /// Cubic Hermite spline
+    ///
+    /// The points are pairs of a position and its tangent, `[p0, m0, p1, m1, ..]`. The tangents
+    /// are relative to a single segment, i.e. the derivative at `pi` is `mi * segments`.
+    Hermite,
+    /// Uniform cubic B-spline, which approximates the points with a continuous curvature
+    BSpline,
+}
+
+/// A cubic spline curve through `N`-dimensional points
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spline<T = f32, const N: usize = 3> {
+    kind: SplineKind,
+    points: Vec<[T; N]>,
+}
+
+/// A lookup table of the arc length of a spline, see `Spline::arc_length_table`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthTable<T = f32> {
+    // the accumulated lengths at uniformly spaced parameters
+    lengths: Vec<T>,
+}
+
+// The number of segments for the given number of points, even if the number is invalid
+fn segment_count(kind: SplineKind, points: usize) -> usize {
+    match kind {
+        SplineKind::Bezier => points.saturating_sub(1) / 3,
+        SplineKind::CatmullRom => points.saturating_sub(1),
+        SplineKind::Hermite => (points / 2).saturating_sub(1),
+        SplineKind::BSpline => points.saturating_sub(3),
+    }
+}
+
+// The number of points for the given number of segments
+fn point_count(kind: SplineKind, segments: usize) -> usize {
+    match kind {
+        SplineKind::Bezier => 3 * segments + 1,
+        SplineKind::CatmullRom => segments + 1,
+        SplineKind::Hermite => 2 * segments + 2,
+        SplineKind::BSpline => segments + 3,
+    }
+}
+
+// The weights of the four points of a segment at the given local parameter `s`
+fn basis<T: Scalar>(kind: SplineKind, s: T) -> [T; 4] {
+    let c = |x: f32| T::from_f32(x);
+    let s2 = s * s;
+    let s3 = s2 * s;
+    let r = T::ONE - s;
+
+    match kind {
+        SplineKind::Bezier => [r * r * r, c(3.) * s * r * r, c(3.) * s2 * r, s3],
+        SplineKind::CatmullRom => [
+            c(0.5) * (-s3 + c(2.) * s2 - s),
+            c(0.5) * (c(3.) * s3 - c(5.) * s2 + c(2.)),
+            c(0.5) * (c(-3.) * s3 + c(4.) * s2 + s),
+            c(0.5) * (s3 - s2),
+        ],
+        SplineKind::Hermite => [
+            c(2.) * s3 - c(3.) * s2 + T::ONE,
+            s3 - c(2.) * s2 + s,
+            c(3.) * s2 - c(2.) * s3,
+            s3 - s2,
+        ],
+        SplineKind::BSpline => [
+            r * r * r / c(6.),
+            (c(3.) * s3 - c(6.) * s2 + c(4.)) / c(6.),
+            (c(-3.) * s3 + c(3.) * s2 + c(3.) * s + T::ONE) / c(6.),
+            s3 / c(6.),
+        ],
+    }
+}
+
+// The derivatives of `basis` with respect to `s`
+fn basis_derivative<T: Scalar>(kind: SplineKind, s: T) -> [T; 4] {
+    let c = |x: f32| T::from_f32(x);
+    let s2 = s * s;
+    let r = T::ONE - s;
+
+    match kind {
+        SplineKind::Bezier => [
+            c(-3.) * r * r,
+            c(3.) * r * r - c(6.) * s * r,
+            c(6.) * s * r - c(3.) * s2,
+            c(3.) * s2,
+        ],
+        SplineKind::CatmullRom => [
+            c(0.5) * (c(-3.) * s2 + c(4.) * s - T::ONE),
+            c(0.5) * (c(9.) * s2 - c(10.) * s),
+            c(0.5) * (c(-9.) * s2 + c(8.) * s + T::ONE),
+            c(0.5) * (c(3.) * s2 - c(2.) * s),
+        ],
+        SplineKind::Hermite => [
+            c(6.) * s2 - c(6.) * s,
+            c(3.) * s2 - c(4.) * s + T::ONE,
+            c(6.) * s - c(6.) * s2,
+            c(3.) * s2 - c(2.) * s,
+        ],
+        SplineKind::BSpline => [
+            c(-0.5) * r * r,
+            c(1.5) * s2 - c(2.) * s,
+            c(-1.5) * s2 + s + c(0.5),
+            c(0.5) * s2,
+        ],
+    }
+}
+
+fn weighted_sum<T: Scalar, const N: usize>(points: &[[T; N]; 4], weights: [T; 4]) -> [T; N] {
+    let mut out = [T::ZERO; N];
+    for (p, &w) in points.iter().zip(weights.iter()) {
+        for (o, &x) in out.iter_mut().zip(p.iter()) {
+            *o += x * w;
+        }
+    }
+    out
+}
+
+fn distance<T: Scalar, const N: usize>(a: &[T; N], b: &[T; N]) -> T {
+    a.iter()
+        .zip(b.iter())
+        .fold(T::ZERO, |acc, (&x, &y)| acc + (y - x) * (y - x))
+        .sqrt()
+}
+
+impl<T: Scalar, const N: usize> Spline<T, N> {
+    /// Create a spline from the given points, see `SplineKind` for their meaning
+    ///
+    /// Fails if the number of points does not form complete segments. At least two points
+    /// are required for Catmull-Rom splines and four points otherwise.
+    pub fn new(kind: SplineKind, points: Vec<[T; N]>) -> Result<Self, MathError> {
+        let segments = segment_count(kind, points.len()).max(1);
+        check_len(points.len(), point_count(kind, segments))?;
+
+        Ok(Spline { kind, points })
+    }
+
+    /// The type of this spline
+    pub fn kind(&self) -> SplineKind {
+        self.kind
+    }
+
+    /// The points of this spline
+    pub fn points(&self) -> &[[T; N]] {
+        &self.points
+    }
+
+    /// The number of cubic segments
+    pub fn segments(&self) -> usize {
+        segment_count(self.kind, self.points.len())
+    }
+
+    // The segment and its local parameter for the given (clamped) curve parameter
+    fn locate(&self, t: T) -> (usize, T) {
+        let segments = self.segments();
+        let t = if t < T::ZERO {
+            T::ZERO
+        } else if t > T::ONE {
+            T::ONE
+        } else {
+            t
+        };
+
+        let u = t * T::from_f32(segments as f32);
+        let i = (u.to_f32() as usize).min(segments - 1);
+        (i, u - T::from_f32(i as f32))
+    }
+
+    // The four points which are weighted by the basis of the given segment
+    fn segment_points(&self, segment: usize) -> [[T; N]; 4] {
+        let p = &self.points;
+        match self.kind {
+            SplineKind::Bezier => [
+                p[3 * segment],
+                p[3 * segment + 1],
+                p[3 * segment + 2],
+                p[3 * segment + 3],
+            ],
+            SplineKind::CatmullRom => {
+                // mirror the missing neighbours at the ends
+                let mirror = |a: &[T; N], b: &[T; N]| {
+                    let mut out = *a;
+                    for (o, &x) in out.iter_mut().zip(b.iter()) {
+                        *o = *o + *o - x;
+                    }
+                    out
+                };
+                let last = p.len() - 1;
+                [
+                    if segment == 0 {
+                        mirror(&p[0], &p[1])
+                    } else {
+                        p[segment - 1]
+                    },
+                    p[segment],
+                    p[segment + 1],
+                    if segment + 1 == last {
+                        mirror(&p[last], &p[last - 1])
+                    } else {
+                        p[segment + 2]
+                    },
+                ]
+            }
+            SplineKind::Hermite => [
+                p[2 * segment],
+                p[2 * segment + 1],
+                p[2 * segment + 2],
+                p[2 * segment + 3],
+            ],
+            SplineKind::BSpline => [p[segment], p[segment + 1], p[segment + 2], p[segment + 3]],
+        }
+    }
+
+    /// Evaluate the position at the given curve parameter in `[0, 1]`
+    ///
+    /// Parameters outside of `[0, 1]` are clamped.
+    pub fn position(&self, t: T) -> [T; N] {
+        let (segment, s) = self.locate(t);
+        weighted_sum(&self.segment_points(segment), basis(self.kind, s))
+    }
+
+    /// Evaluate the derivative with respect to the curve parameter, i.e. the velocity
+    /// if the whole curve is traversed in one unit of time
+    pub fn derivative(&self, t: T) -> [T; N] {
+        let (segment, s) = self.locate(t);
+        let mut out = weighted_sum(
+            &self.segment_points(segment),
+            basis_derivative(self.kind, s),
+        );
+
+        let segments = T::from_f32(self.segments() as f32);
+        for x in &mut out {
+            *x *= segments;
+        }
+        out
+    }
+
+    /// Approximate the arc length by a polyline of `samples` uniformly spaced segments
+    ///
+    /// The table maps distances along the curve to curve parameters, see `resample`.
+    pub fn arc_length_table(&self, samples: usize) -> ArcLengthTable<T> {
+        let samples = samples.max(1);
+        let step = T::ONE / T::from_f32(samples as f32);
+
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut length = T::ZERO;
+        let mut prev = self.position(T::ZERO);
+        lengths.push(length);
+        for i in 1..=samples {
+            let next = self.position(step * T::from_f32(i as f32));
+            length += distance(&prev, &next);
+            lengths.push(length);
+            prev = next;
+        }
+
+        ArcLengthTable { lengths }
+    }
+
+    /// Evaluate the position at the given distance along the curve
+    pub fn position_at_distance(&self, table: &ArcLengthTable<T>, distance: T) -> [T; N] {
+        self.position(table.parameter(distance))
+    }
+
+    /// Sample `count` points with equal spacing along the curve, including both ends
+    ///
+    /// The points are flattened into a single buffer, e.g. for a `LINE_STRIP` vertex buffer.
+    /// `table` has to be computed for this spline, more samples result in a more even spacing.
+    pub fn resample(&self, table: &ArcLengthTable<T>, count: usize) -> Vec<f32> {
+        let mut out = Vec::with_capacity(count * N);
+        let step = if count > 1 {
+            table.length() / T::from_f32((count - 1) as f32)
+        } else {
+            T::ZERO
+        };
+
+        for i in 0..count {
+            let p = self.position_at_distance(table, step * T::from_f32(i as f32));
+            out.extend(p.iter().map(|x| x.to_f32()));
+        }
+        out
+    }
+}
+
+impl<T: Scalar> ArcLengthTable<T> {
+    /// The total length of the curve
+    pub fn length(&self) -> T {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// The curve parameter at the given distance along the curve
+    ///
+    /// Distances outside of `[0, length]` are clamped.
+    pub fn parameter(&self, distance: T) -> T {
+        let last = self.lengths.len() - 1;
+        let samples = T::from_f32(last as f32);
+
+        // the number of samples at or before `distance`
+        let next = self.lengths.partition_point(|&l| l <= distance);
+        if next == 0 {
+            return T::ZERO;
+        }
+        if next > last {
+            return T::ONE;
+        }
+
+        let prev = next - 1;
+        let dl = self.lengths[next] - self.lengths[prev];
+        let s = if dl > T::ZERO {
+            (distance - self.lengths[prev]) / dl
+        } else {
+            T::ZERO
+        };
+        (T::from_f32(prev as f32) + s) / samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{almost_eq, almost_eq_relative};
+
+    const KINDS: [SplineKind; 4] = [
+        SplineKind::Bezier,
+        SplineKind::CatmullRom,
+        SplineKind::Hermite,
+        SplineKind::BSpline,
+    ];
+
+    fn points() -> Vec<[f32; 3]> {
+        vec![
+            [0., 0., 0.],
+            [1., 2., 0.],
+            [3., 2., 1.],
+            [4., 0., 1.],
+            [5., -1., 0.],
+            [7., 1., 2.],
+            [8., 0., 0.],
+        ]
+    }
+
+    #[test]
+    fn spline_new() {
+        assert_eq!(
+            Spline::new(SplineKind::Bezier, points()[..6].to_vec()),
+            Err(MathError::DimensionMismatch {
+                expected: 4,
+                found: 6
+            })
+        );
+        assert_eq!(
+            Spline::new(SplineKind::Hermite, points()[..5].to_vec()),
+            Err(MathError::DimensionMismatch {
+                expected: 4,
+                found: 5
+            })
+        );
+        assert_eq!(
+            Spline::new(SplineKind::CatmullRom, vec![[0., 0.]]),
+            Err(MathError::DimensionMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert!(Spline::<f32, 2>::new(SplineKind::BSpline, vec![]).is_err());
+
+        for &(kind, segments) in &[
+            (SplineKind::Bezier, 2),
+            (SplineKind::CatmullRom, 6),
+            (SplineKind::BSpline, 4),
+        ] {
+            let spline = Spline::new(kind, points()).unwrap();
+            assert_eq!(spline.segments(), segments);
+        }
+    }
+
+    #[test]
+    fn spline_bezier() {
+        let spline = Spline::new(SplineKind::Bezier, points()).unwrap();
+        assert_eq!(spline.position(0.), [0., 0., 0.]);
+        assert_eq!(spline.position(0.5), [4., 0., 1.]);
+        assert_eq!(spline.position(2.), [8., 0., 0.]);
+
+        // the end tangents point to the control points
+        assert!(almost_eq(&spline.derivative(0.), &[6., 12., 0.]));
+        assert!(almost_eq(&spline.derivative(1.), &[6., -6., -12.]));
+    }
+
+    #[test]
+    fn spline_catmull_rom() {
+        let points = points();
+        let spline = Spline::new(SplineKind::CatmullRom, points.clone()).unwrap();
+
+        // passes through all points with the tangent `(p[i + 1] - p[i - 1]) / 2`
+        for (i, p) in points.iter().enumerate() {
+            assert!(almost_eq(&spline.position(i as f32 / 6.), p));
+        }
+        assert!(almost_eq(&spline.derivative(1. / 6.), &[9., 6., 3.]));
+
+        // two points result in a line with constant speed
+        let line = Spline::new(SplineKind::CatmullRom, vec![[1., 1.], [3., 5.]]).unwrap();
+        assert!(almost_eq(&line.position(0.25), &[1.5, 2.]));
+        assert!(almost_eq(&line.derivative(0.75), &[2., 4.]));
+    }
+
+    #[test]
+    fn spline_hermite() {
+        // zero tangents result in a smoothstep
+        let zero = [0., 0.];
+        let spline =
+            Spline::new(SplineKind::Hermite, vec![[0., 0.], zero, [2., 4.], zero]).unwrap();
+        assert!(almost_eq(
+            &spline.position(0.25),
+            &[2. * 0.15625, 4. * 0.15625]
+        ));
+        assert!(almost_eq(&spline.derivative(0.), &zero));
+        assert!(almost_eq(&spline.derivative(0.5), &[3., 6.]));
+
+        // the tangents are per segment
+        let spline = Spline::new(
+            SplineKind::Hermite,
+            vec![[0., 0.], [1., 0.], [1., 0.], [1., 0.], [2., 0.], [1., 0.]],
+        )
+        .unwrap();
+        assert!(almost_eq(&spline.position(0.75), &[1.5, 0.]));
+        assert!(almost_eq(&spline.derivative(0.3), &[2., 0.]));
+    }
+
+    #[test]
+    fn spline_bspline() {
+        let spline = Spline::new(SplineKind::BSpline, points()).unwrap();
+        assert!(almost_eq(
+            &spline.position(0.),
+            &[7. / 6., 5. / 3., 1. / 6.]
+        ));
+
+        // evenly spaced collinear points result in a line
+        let line = Spline::new(
+            SplineKind::BSpline,
+            vec![[0., 0.], [1., 2.], [2., 4.], [3., 6.], [4., 8.]],
+        )
+        .unwrap();
+        assert!(almost_eq(&line.position(0.), &[1., 2.]));
+        assert!(almost_eq(&line.position(0.5), &[2., 4.]));
+        assert!(almost_eq(&line.derivative(0.7), &[2., 4.]));
+    }
+
+    #[test]
+    fn spline_derivative() {
+        // compare with central differences
+        let h = 1e-5;
+        for &kind in &KINDS {
+            // Hermite splines require an even number of points
+            let count = if kind == SplineKind::Hermite { 6 } else { 7 };
+            let points = points()[..count]
+                .iter()
+                .map(|p| [p[0] as f64, p[1] as f64, p[2] as f64])
+                .collect();
+            let spline = Spline::new(kind, points).unwrap();
+
+            for &t in &[0.1, 0.35, 0.6, 0.9] {
+                let a = spline.position(t - h);
+                let b = spline.position(t + h);
+                let expected = [
+                    (b[0] - a[0]) / (2. * h),
+                    (b[1] - a[1]) / (2. * h),
+                    (b[2] - a[2]) / (2. * h),
+                ];
+                assert!(
+                    almost_eq_relative(&spline.derivative(t), &expected, 1e-4, 1e-4),
+                    "{:?} at {}",
+                    kind,
+                    t
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn spline_arc_length() {
+        // the control points slow down the curve towards the end
+        let spline = Spline::new(
+            SplineKind::Bezier,
+            vec![[0., 0.], [6., 0.], [9., 0.], [9., 0.]],
+        )
+        .unwrap();
+        let table = spline.arc_length_table(256);
+        assert!((table.length() - 9.).abs() < 1e-4);
+        assert_eq!(table.parameter(-1.), 0.);
+        assert_eq!(table.parameter(10.), 1.);
+        assert!(almost_eq_relative(
+            &spline.position_at_distance(&table, 4.5),
+            &[4.5, 0.],
+            1e-3,
+            0.
+        ));
+
+        let vertices = spline.resample(&table, 10);
+        assert_eq!(vertices.len(), 20);
+        for (i, p) in vertices.chunks_exact(2).enumerate() {
+            assert!(almost_eq_relative(p, &[i as f32, 0.], 1e-3, 0.), "{:?}", p);
+        }
+
+        assert_eq!(spline.resample(&table, 1), vec![0., 0.]);
+        assert!(spline.resample(&table, 0).is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::mat4::Mat4;
+use crate::quat::{Quat, Quaternion};
+use crate::scalar::Scalar;
+use crate::vec3::Vec3;
+
+/// A transformation decomposed into translation, rotation and scale
+///
+/// The composed matrix applies the scale first, then the rotation and finally the translation.
+/// It can be built step by step:
+///
+/// ```rust
+/// use webgl_matrix::{Quat, Quaternion, Trs};
+///
+/// let m = Trs::identity()
+///     .with_translation([1., 2., 3.])
+///     .with_rotation(Quat::from_axis_angle(&[0., 1., 0.], 0.5))
+///     .with_scale([2., 2., 2.])
+///     .to_mat4();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trs<T = f32> {
+    pub translation: Vec3<T>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::quat"))]
+    pub rotation: Quat<T>,
+    pub scale: Vec3<T>,
+}
+
+impl<T: Scalar> Trs<T> {
+    /// Create a transformation from its parts
+    pub fn new(translation: Vec3<T>, rotation: Quat<T>, scale: Vec3<T>) -> Self {
+        Trs {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Create the identity transformation
+    pub fn identity() -> Self {
+        Trs {
+            translation: [T::ZERO; 3],
+            rotation: Quat::identity(),
+            scale: [T::ONE; 3],
+        }
+    }
+
+    /// Replace the translation
+    pub fn with_translation(mut self, translation: Vec3<T>) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Replace the rotation
+    pub fn with_rotation(mut self, rotation: Quat<T>) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Replace the scale
+    pub fn with_scale(mut self, scale: Vec3<T>) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Compose the transformation matrix
+    pub fn to_mat4(&self) -> Mat4<T> {
+        let mut m = self.rotation.to_mat4();
+        for (col, &s) in self.scale.iter().enumerate() {
+            m[col * 4] *= s;
+            m[col * 4 + 1] *= s;
+            m[col * 4 + 2] *= s;
+        }
+        m[12] = self.translation[0];
+        m[13] = self.translation[1];
+        m[14] = self.translation[2];
+
+        m
+    }
+
+    /// Decompose the given affine transformation matrix
+    ///
+    /// Shear cannot be represented and is lost. A reflection is represented by a negative
+    /// scale along the X-axis.
+    pub fn from_mat4(m: &Mat4<T>) -> Self {
+        let len = |i: usize| (m[i] * m[i] + m[i + 1] * m[i + 1] + m[i + 2] * m[i + 2]).sqrt();
+        let mut scale = [len(0), len(4), len(8)];
+
+        let det = m[0] * (m[5] * m[10] - m[6] * m[9]) - m[4] * (m[1] * m[10] - m[2] * m[9])
+            + m[8] * (m[1] * m[6] - m[2] * m[5]);
+        if det < T::ZERO {
+            scale[0] = -scale[0];
+        }
+
+        let mut r = *m;
+        for (col, &s) in scale.iter().enumerate() {
+            if s.abs() > T::EPSILON {
+                r[col * 4] /= s;
+                r[col * 4 + 1] /= s;
+                r[col * 4 + 2] /= s;
+            }
+        }
+
+        Trs {
+            translation: [m[12], m[13], m[14]],
+            rotation: Quat::from_mat4(&r).normalize(),
+            scale,
+        }
+    }
+}
+
+impl<T: Scalar> Default for Trs<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Matrix;
+    use crate::utils::almost_eq;
+
+    #[test]
+    fn trs_to_mat4() {
+        let rotation: Quat = Quat::from_axis_angle(&[1., 2., 3.], 0.8);
+        let trs = Trs::identity()
+            .with_translation([1., 2., 3.])
+            .with_rotation(rotation)
+            .with_scale([2., 3., 4.]);
+
+        // `a.mul(&b)` computes `b * a` in column-major notation, so this is `T * R * S`
+        let mut expected: Mat4 = Mat4::identity();
+        expected[0] = 2.;
+        expected[5] = 3.;
+        expected[10] = 4.;
+        expected.mul(&rotation.to_mat4());
+        let mut translation = Mat4::identity();
+        translation.translate(&[1., 2., 3.]);
+        expected.mul(&translation);
+
+        assert!(almost_eq(&trs.to_mat4(), &expected));
+    }
+
+    #[test]
+    fn trs_decompose_round_trip() {
+        let trs = Trs::new(
+            [-4., 0.5, 7.],
+            Quat::from_axis_angle(&[0., 1., 1.], 2.5),
+            [1., 0.5, 3.],
+        );
+
+        let decomposed = Trs::from_mat4(&trs.to_mat4());
+        assert!(almost_eq(&decomposed.translation, &trs.translation));
+        assert!(almost_eq(&decomposed.rotation, &trs.rotation));
+        assert!(almost_eq(&decomposed.scale, &trs.scale));
+    }
+
+    #[test]
+    fn trs_decompose_reflection() {
+        let trs = Trs::identity().with_scale([-2., 1., 1.]);
+
+        let decomposed = Trs::from_mat4(&trs.to_mat4());
+        assert!(almost_eq(&decomposed.scale, &[-2., 1., 1.]));
+        assert!(almost_eq(&decomposed.to_mat4(), &trs.to_mat4()));
+    }
+}
@@ -1,18 +1,286 @@
-// we redfine it, because of no_std
-// pub const EPSILON : f32 = 1.19209290e-07_f32;
-pub const EPSILON: f32 = 1e-5_f32;
+use crate::scalar::Scalar;
 
-/// Checks if two sequences of numbers are equal up to EPSILON precision.
-pub fn almost_eq(a: &[f32], b: &[f32]) -> bool {
+/// The tolerance used for `f32` comparisons, same as `<f32 as Scalar>::EPSILON`
+pub const EPSILON: f32 = <f32 as Scalar>::EPSILON;
+
+/// Checks if two sequences of numbers are equal up to `Scalar::EPSILON` precision.
+pub fn almost_eq<T: Scalar>(a: &[T], b: &[T]) -> bool {
     if a.len() == b.len() {
         a.iter()
             .zip(b.iter())
-            .all(|(&ai, &bi)| ai - EPSILON <= bi && ai + EPSILON >= bi)
+            .all(|(&ai, &bi)| ai - T::EPSILON <= bi && ai + T::EPSILON >= bi)
+    } else {
+        false
+    }
+}
+
+/// Checks if two sequences of numbers are equal up to an absolute or a relative tolerance.
+///
+/// Two numbers are considered equal if their difference is at most `abs_tol` (useful near zero)
+/// or at most `rel_tol` times the larger magnitude of both (useful for large values).
+pub fn almost_eq_relative<T: Scalar>(a: &[T], b: &[T], abs_tol: T, rel_tol: T) -> bool {
+    if a.len() == b.len() {
+        a.iter().zip(b.iter()).all(|(&ai, &bi)| {
+            let diff = (ai - bi).abs();
+            let largest = if ai.abs() > bi.abs() {
+                ai.abs()
+            } else {
+                bi.abs()
+            };
+            diff <= abs_tol || diff <= largest * rel_tol
+        })
     } else {
         false
     }
 }
 
+/// Floating point types which can be compared by the number of representable values between them
+pub trait Ulps: Copy {
+    /// The number of representable values between `self` and `other`, `None` for NaN
+    fn ulps(self, other: Self) -> Option<u64>;
+}
+
+macro_rules! impl_ulps {
+    ($type:ident, $int:ident, $wide:ident) => {
+        impl Ulps for $type {
+            fn ulps(self, other: $type) -> Option<u64> {
+                if self.is_nan() || other.is_nan() {
+                    return None;
+                }
+
+                // map the bits to integers which are ordered like the floats, with -0 == +0
+                let ordered = |x: $type| {
+                    let i = $wide::from(x.to_bits() as $int);
+                    if i < 0 {
+                        $wide::from($int::MIN) - i
+                    } else {
+                        i
+                    }
+                };
+                let diff = (ordered(self) - ordered(other)).unsigned_abs();
+                Some(diff.min(u64::MAX.into()) as u64)
+            }
+        }
+    };
+}
+
+impl_ulps!(f32, i32, i64);
+impl_ulps!(f64, i64, i128);
+
+/// Checks if two sequences of numbers are at most `max_ulps` representable values apart.
+///
+/// Unlike the other comparisons this scales with the magnitude of the numbers automatically,
+/// but it is very strict close to zero. NaNs are never equal.
+pub fn almost_eq_ulps<T: Ulps>(a: &[T], b: &[T], max_ulps: u64) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .all(|(&ai, &bi)| ai.ulps(bi).is_some_and(|ulps| ulps <= max_ulps))
+}
+
+/// Clamps `x` into `[min, max]`, same as GLSL `clamp`
+pub fn clamp<T: Scalar>(x: T, min: T, max: T) -> T {
+    let x = if x > min { x } else { min };
+    if x < max {
+        x
+    } else {
+        max
+    }
+}
+
+/// Linearly interpolates between `a` (`t = 0`) and `b` (`t = 1`), same as GLSL `mix`
+///
+/// `t` is not clamped, i.e. values outside of `[0, 1]` extrapolate.
+pub fn lerp<T: Scalar>(a: T, b: T, t: T) -> T {
+    a * (T::ONE - t) + b * t
+}
+
+/// The inverse of `lerp`, i.e. the parameter `t` for which `lerp(a, b, t) == value`
+///
+/// The result is not clamped. It is undefined if `a == b`.
+pub fn inverse_lerp<T: Scalar>(a: T, b: T, value: T) -> T {
+    (value - a) / (b - a)
+}
+
+/// Maps `value` from the range `[in_min, in_max]` to the range `[out_min, out_max]`
+///
+/// The result is not clamped. It is undefined if `in_min == in_max`.
+pub fn remap<T: Scalar>(in_min: T, in_max: T, out_min: T, out_max: T, value: T) -> T {
+    lerp(out_min, out_max, inverse_lerp(in_min, in_max, value))
+}
+
+/// Hermite interpolation between `0` at `edge0` and `1` at `edge1`, same as GLSL `smoothstep`
+///
+/// Like in GLSL, the result is undefined if `edge0 == edge1`.
+pub fn smoothstep<T: Scalar>(edge0: T, edge1: T, x: T) -> T {
+    let t = clamp((x - edge0) / (edge1 - edge0), T::ZERO, T::ONE);
+    t * t * (T::from_f32(3.) - T::from_f32(2.) * t)
+}
+
+/// Same as `smoothstep`, but with zero first and second derivatives at the edges
+pub fn smootherstep<T: Scalar>(edge0: T, edge1: T, x: T) -> T {
+    let t = clamp((x - edge0) / (edge1 - edge0), T::ZERO, T::ONE);
+    t * t * t * (t * (t * T::from_f32(6.) - T::from_f32(15.)) + T::from_f32(10.))
+}
+
+/// Computes `x` modulo `y`, same as GLSL `mod`
+///
+/// Unlike `%`, the result has the sign of `y`, e.g. `modulo(-1., 3.) == 2.`.
+pub fn modulo<T: Scalar>(x: T, y: T) -> T {
+    x - y * (x / y).floor()
+}
+
+// π in the precision of `T`
+pub(crate) fn pi<T: Scalar>() -> T {
+    (-T::ONE).acos()
+}
+
+/// Wraps the given angle (radians) into `[-π, π)`
+pub fn wrap_angle<T: Scalar>(angle: T) -> T {
+    let pi = pi::<T>();
+    modulo(angle + pi, pi + pi) - pi
+}
+
+/// Wraps the given angle (radians) into `[0, 2π)`
+pub fn wrap_angle_positive<T: Scalar>(angle: T) -> T {
+    let pi = pi::<T>();
+    modulo(angle, pi + pi)
+}
+
+/// The signed difference `to - from` of two angles (radians) along the shorter direction,
+/// wrapped into `[-π, π)`
+pub fn angle_difference<T: Scalar>(from: T, to: T) -> T {
+    wrap_angle(to - from)
+}
+
+/// Interpolates between two angles (radians) along the shorter direction
+///
+/// The result is not wrapped, i.e. it lies between `from` and `from + angle_difference(from, to)`.
+pub fn lerp_angle<T: Scalar>(from: T, to: T, t: T) -> T {
+    from + angle_difference(from, to) * t
+}
+
+// Formats 9 and 16 values as matrix grid, everything else as a single row
+#[doc(hidden)]
+pub fn format_grid<T: Scalar>(values: &[T]) -> String {
+    let cells: Vec<String> = values.iter().map(|x| format!("{:?}", x)).collect();
+    let mut out = String::new();
+    crate::format::write_grid(&mut out, &cells, crate::format::Layout::RowMajor, "  ").unwrap();
+    out
+}
+
+/// Asserts that two vectors or matrices are almost equal, printing both as grids on failure.
+///
+/// Without tolerances `almost_eq` is used, otherwise `almost_eq_relative`:
+///
+/// ```rust
+/// use webgl_matrix::assert_almost_eq;
+///
+/// assert_almost_eq!([1f32, 2., 3.], [1., 2., 3.000001]);
+/// assert_almost_eq!([1e6f32, 0.], [1.00001e6, 1e-7], 1e-6, 1e-4);
+/// ```
+#[macro_export]
+macro_rules! assert_almost_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_almost_eq!(@check $left, $right, |l, r| $crate::utils::almost_eq(l, r))
+    };
+    ($left:expr, $right:expr, $abs_tol:expr, $rel_tol:expr $(,)?) => {
+        $crate::assert_almost_eq!(@check $left, $right, |l, r| {
+            $crate::utils::almost_eq_relative(l, r, $abs_tol, $rel_tol)
+        })
+    };
+    (@check $left:expr, $right:expr, $eq:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[_] = ::std::convert::AsRef::as_ref(left);
+                let right: &[_] = ::std::convert::AsRef::as_ref(right);
+                if !$eq(left, right) {
+                    panic!(
+                        "assertion failed: `{} ≈ {}`\n left:\n{} right:\n{}",
+                        stringify!($left),
+                        stringify!($right),
+                        $crate::utils::format_grid(left),
+                        $crate::utils::format_grid(right),
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(
+    feature = "approx",
+    any(
+        all(feature = "Operators", any(feature = "Vector3", feature = "Vector4")),
+        all(feature = "Matrix4", feature = "Quaternion")
+    )
+))]
+mod approx_impls {
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+    // Delegates to the implementations for slices of the given fields
+    macro_rules! impl_approx {
+        ($type:ident, $($field:tt),+) => {
+            impl<T: AbsDiffEq> AbsDiffEq for crate::$type<T>
+            where
+                T::Epsilon: Copy,
+            {
+                type Epsilon = T::Epsilon;
+
+                fn default_epsilon() -> T::Epsilon {
+                    T::default_epsilon()
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: T::Epsilon) -> bool {
+                    $(self.$field[..].abs_diff_eq(&other.$field[..], epsilon))&&+
+                }
+            }
+
+            impl<T: RelativeEq> RelativeEq for crate::$type<T>
+            where
+                T::Epsilon: Copy,
+            {
+                fn default_max_relative() -> T::Epsilon {
+                    T::default_max_relative()
+                }
+
+                fn relative_eq(
+                    &self,
+                    other: &Self,
+                    epsilon: T::Epsilon,
+                    max_relative: T::Epsilon,
+                ) -> bool {
+                    $(self.$field[..].relative_eq(&other.$field[..], epsilon, max_relative))&&+
+                }
+            }
+
+            impl<T: UlpsEq> UlpsEq for crate::$type<T>
+            where
+                T::Epsilon: Copy,
+            {
+                fn default_max_ulps() -> u32 {
+                    T::default_max_ulps()
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: T::Epsilon, max_ulps: u32) -> bool {
+                    $(self.$field[..].ulps_eq(&other.$field[..], epsilon, max_ulps))&&+
+                }
+            }
+        };
+    }
+
+    #[cfg(all(feature = "Operators", feature = "Vector3"))]
+    impl_approx!(V3, 0);
+    #[cfg(all(feature = "Operators", feature = "Vector4"))]
+    impl_approx!(V4, 0);
+    #[cfg(all(feature = "Operators", feature = "Matrix3"))]
+    impl_approx!(M3, 0);
+    #[cfg(all(feature = "Operators", feature = "Matrix4"))]
+    impl_approx!(M4, 0);
+    #[cfg(all(feature = "Matrix4", feature = "Quaternion"))]
+    impl_approx!(Trs, translation, rotation, scale);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,14 +295,154 @@ mod tests {
 
     #[test]
     fn almost_eq_if_not_equal() {
-        let a = [1., 2., 3., 4.];
+        let a: [f32; 4] = [1., 2., 3., 4.];
         let b = [1., 2.001, 3., 4.];
 
         assert!(!almost_eq(&a, &b));
 
-        let a = [1., 2., 3., 4.];
+        let a: [f32; 4] = [1., 2., 3., 4.];
         let b = [1., 2., 2.9999, 4.];
 
         assert!(!almost_eq(&a, &b));
     }
+
+    #[test]
+    fn almost_eq_uses_scalar_epsilon() {
+        let a: [f32; 2] = [1., 2.];
+        let b = [1., 2. + 1e-7];
+        assert!(almost_eq(&a, &b));
+
+        let a: [f64; 2] = [1., 2.];
+        let b = [1., 2. + 1e-7];
+        assert!(!almost_eq(&a, &b));
+    }
+
+    #[test]
+    fn almost_eq_relative_large_values() {
+        let a: [f32; 2] = [1e5, 0.];
+        let b = [1e5 + 0.01, 1e-7];
+
+        assert!(!almost_eq(&a, &b));
+        assert!(almost_eq_relative(&a, &b, 1e-6, 1e-6));
+        assert!(!almost_eq_relative(&a, &b, 1e-6, 1e-8));
+        assert!(!almost_eq_relative(&a, &b[..1], 1., 1.));
+    }
+
+    #[test]
+    fn almost_eq_relative_near_zero() {
+        let a: [f32; 1] = [1e-7];
+        let b = [-1e-7];
+
+        // the relative tolerance alone is useless around zero
+        assert!(!almost_eq_relative(&a, &b, 0., 0.1));
+        assert!(almost_eq_relative(&a, &b, 1e-6, 0.));
+    }
+
+    #[test]
+    fn almost_eq_ulps_distance() {
+        let next = f32::from_bits(1f32.to_bits() + 2);
+        assert_eq!(1f32.ulps(next), Some(2));
+        assert_eq!(0f32.ulps(-0.), Some(0));
+        assert_eq!(f32::from_bits(1).ulps(-f32::from_bits(1)), Some(2));
+        assert_eq!(1f64.ulps(f64::NAN), None);
+        assert_eq!(f64::MAX.ulps(f64::MIN), Some(0xFFDF_FFFF_FFFF_FFFE));
+
+        assert!(almost_eq_ulps(&[1f32, 1e30], &[next, 1e30], 2));
+        assert!(!almost_eq_ulps(&[1f32], &[next], 1));
+        assert!(!almost_eq_ulps(&[f32::NAN], &[f32::NAN], 4));
+    }
+
+    #[test]
+    fn almost_eq_assert_macro() {
+        let a: [f32; 9] = [1., 0., 0., 0., 1., 0., 0., 0., 1.];
+        crate::assert_almost_eq!(a, [1., 0., 0., 0., 1., 0., 0., 0., 1.000001]);
+        crate::assert_almost_eq!(vec![1e6f32], [1.00001e6], 0., 1e-4);
+
+        let result = std::panic::catch_unwind(|| {
+            crate::assert_almost_eq!(a, [1., 0., 0., 2., 1., 0., 0., 0., 1.]);
+        });
+        let message = result.unwrap_err();
+        let message = message.downcast_ref::<String>().unwrap();
+        assert!(message.contains("  [1.0, 2.0, 0.0]\n"));
+
+        // vectors and quaternions are printed as a single row
+        let result = std::panic::catch_unwind(|| {
+            crate::assert_almost_eq!([1f32, 2., 3., 4.], [1f32, 2., 3., 5.]);
+        });
+        let message = result.unwrap_err();
+        let message = message.downcast_ref::<String>().unwrap();
+        assert!(message.contains("  [1.0, 2.0, 3.0, 4.0]\n"));
+        assert!(message.contains("  [1.0, 2.0, 3.0, 5.0]\n"));
+    }
+
+    #[test]
+    fn interpolation_smoothstep() {
+        assert_eq!(smoothstep(1., 3., 0.), 0.);
+        assert_eq!(smoothstep(1., 3., 2.), 0.5);
+        assert_eq!(smoothstep(1., 3., 2.5), 0.84375);
+        assert_eq!(smoothstep(1., 3., 4.), 1.);
+        // reversed edges invert the step, as in GLSL
+        assert_eq!(smoothstep(3., 1., 2.5), 0.15625);
+
+        assert_eq!(smootherstep(0., 1., 0.5), 0.5);
+        assert_eq!(smootherstep(0., 2., 0.5), 0.103515625);
+        assert_eq!(smootherstep(0., 1., -1.), 0.);
+        assert_eq!(smootherstep(0., 1., 2.), 1.);
+    }
+
+    #[test]
+    fn interpolation_remap() {
+        assert_eq!(lerp(2., 4., 0.25), 2.5);
+        assert_eq!(lerp(2., 4., 1.5), 5.);
+        assert_eq!(inverse_lerp(2., 4., 2.5), 0.25);
+        assert_eq!(inverse_lerp(4., 2., 5.), -0.5);
+
+        assert_eq!(remap(0., 10., 100., 200., 2.5), 125.);
+        assert_eq!(remap(-1., 1., 0., 1., 3.), 2.);
+        assert_eq!(clamp(remap(-1., 1., 0., 1., 3.), 0., 1.), 1.);
+    }
+
+    #[test]
+    fn interpolation_angles() {
+        use std::f64::consts::PI;
+
+        assert_eq!(modulo(7., 3.), 1.);
+        assert_eq!(modulo(-1., 3.), 2.);
+        assert_eq!(modulo(1., -3.), -2.);
+
+        assert!(almost_eq(&[wrap_angle(3. * PI)], &[-PI]));
+        assert!(almost_eq(&[wrap_angle(-PI)], &[-PI]));
+        assert!(almost_eq(&[wrap_angle(0.5 - 4. * PI)], &[0.5]));
+        assert!(almost_eq(&[wrap_angle_positive(-0.5)], &[2. * PI - 0.5]));
+        assert!(almost_eq(&[wrap_angle_positive(5. * PI)], &[PI]));
+
+        // the shorter direction crosses ±π
+        let (from, to) = (PI - 0.1, -PI + 0.3);
+        assert!(almost_eq(&[angle_difference(from, to)], &[0.4]));
+        assert!(almost_eq(&[angle_difference(to, from)], &[-0.4]));
+        assert!(almost_eq(&[lerp_angle(from, to, 0.5)], &[PI + 0.1]));
+    }
+
+    #[test]
+    #[cfg(all(feature = "approx", feature = "Operators", feature = "Matrix4"))]
+    fn almost_eq_approx_traits() {
+        use crate::{Mat4, Matrix, M4};
+
+        let a = M4(Mat4::<f32>::identity());
+        let mut b = a;
+        b[12] = 1e-7;
+
+        approx::assert_abs_diff_eq!(a, b, epsilon = 1e-6);
+        approx::assert_relative_ne!(a, b, epsilon = 1e-8, max_relative = 1e-8);
+        approx::assert_ulps_ne!(a, b, epsilon = 0., max_ulps = 4);
+    }
+
+    #[test]
+    #[cfg(all(feature = "approx", feature = "Matrix4", feature = "Quaternion"))]
+    fn almost_eq_approx_trs() {
+        use crate::Trs;
+
+        let t = Trs::identity().with_scale([1e5f32, 1., 1.]);
+        approx::assert_relative_eq!(t, t.with_scale([1e5 + 0.01, 1., 1.]), max_relative = 1e-6);
+    }
 }
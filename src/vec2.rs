@@ -0,0 +1,7 @@
+use crate::scalar::Scalar;
+use crate::slice_ops::*;
+use crate::vector::Vector;
+
+pub type Vec2<T = f32> = [T; 2];
+
+impl_vector!(Vec2, 2);
@@ -2,23 +2,23 @@
 use crate::mat3::Mat3;
 #[cfg(feature = "Matrix4")]
 use crate::mat4::Mat4;
+use crate::scalar::Scalar;
 use crate::slice_ops::*;
 #[cfg(feature = "Matrix4")]
 use crate::vec4::Vec4;
 #[cfg(any(feature = "Matrix3", feature = "Matrix4"))]
 use crate::vector::MulVectorMatrix;
 use crate::vector::Vector;
-use std::f32;
 
-pub type Vec3 = [f32; 3];
+pub type Vec3<T = f32> = [T; 3];
 
 impl_vector!(Vec3, 3);
 
 #[cfg(feature = "Matrix3")]
-impl MulVectorMatrix<Mat3> for Vec3 {
-    type VectorType = Vec3;
+impl<T: Scalar> MulVectorMatrix<Mat3<T>> for Vec3<T> {
+    type VectorType = Vec3<T>;
 
-    fn mul_matrix_left(&self, lhs: &Mat3) -> Self::VectorType {
+    fn mul_matrix_left(&self, lhs: &Mat3<T>) -> Self::VectorType {
         let x = self[0];
         let y = self[1];
         let z = self[2];
@@ -30,7 +30,7 @@ impl MulVectorMatrix<Mat3> for Vec3 {
         ]
     }
 
-    fn mul_matrix(&self, rhs: &Mat3) -> Self::VectorType {
+    fn mul_matrix(&self, rhs: &Mat3<T>) -> Self::VectorType {
         let x = self[0];
         let y = self[1];
         let z = self[2];
@@ -44,12 +44,12 @@ impl MulVectorMatrix<Mat3> for Vec3 {
 }
 
 #[cfg(feature = "Matrix4")]
-impl MulVectorMatrix<Mat4> for Vec3 {
-    type VectorType = Vec4;
+impl<T: Scalar> MulVectorMatrix<Mat4<T>> for Vec3<T> {
+    type VectorType = Vec4<T>;
 
     /// Interprets `self` as a column vector with the 4th component equal to 1 and multiplies the given matrix
     /// from the left-hand-side, i.e. `lhs * [...self, 1.0]`
-    fn mul_matrix_left(&self, lhs: &Mat4) -> Self::VectorType {
+    fn mul_matrix_left(&self, lhs: &Mat4<T>) -> Self::VectorType {
         let x = self[0];
         let y = self[1];
         let z = self[2];
@@ -65,7 +65,7 @@ impl MulVectorMatrix<Mat4> for Vec3 {
 
     /// Interprets `self` as a row vector with the 4th component equal to 1 and multiplies the given matrix
     /// from the right-hand-side, i.e. `[...self, 1.0] * rhs`
-    fn mul_matrix(&self, rhs: &Mat4) -> Self::VectorType {
+    fn mul_matrix(&self, rhs: &Mat4<T>) -> Self::VectorType {
         let x = self[0];
         let y = self[1];
         let z = self[2];
@@ -81,10 +81,10 @@ impl MulVectorMatrix<Mat4> for Vec3 {
 }
 
 pub trait CrossProduct {
-    fn cross(&self, v: &Vec3) -> Vec3;
+    fn cross(&self, v: &Self) -> Self;
 }
 
-impl CrossProduct for Vec3 {
+impl<T: Scalar> CrossProduct for Vec3<T> {
     fn cross(&self, v: &Self) -> Self {
         let u = self;
 
@@ -198,8 +198,8 @@ mod tests {
     }
     #[test]
     fn vec3_mag() {
-        let b = [2., 3., 4.];
-        assert!(almost_eq(&[b.mag()], &[5.385164807]));
+        let b: Vec3 = [2., 3., 4.];
+        assert!(almost_eq(&[b.mag()], &[5.385_165]));
     }
     #[test]
     fn vec3_mag2() {
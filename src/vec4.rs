@@ -1,20 +1,20 @@
 #[cfg(feature = "Matrix4")]
 use crate::mat4::Mat4;
+use crate::scalar::Scalar;
 use crate::slice_ops::*;
 #[cfg(feature = "Matrix4")]
 use crate::vector::MulVectorMatrix;
 use crate::vector::Vector;
-use std::f32;
 
-pub type Vec4 = [f32; 4];
+pub type Vec4<T = f32> = [T; 4];
 
 impl_vector!(Vec4, 4);
 
 #[cfg(feature = "Matrix4")]
-impl MulVectorMatrix<Mat4> for Vec4 {
-    type VectorType = Vec4;
+impl<T: Scalar> MulVectorMatrix<Mat4<T>> for Vec4<T> {
+    type VectorType = Vec4<T>;
 
-    fn mul_matrix_left(&self, lhs: &Mat4) -> Self::VectorType {
+    fn mul_matrix_left(&self, lhs: &Mat4<T>) -> Self::VectorType {
         let x = self[0];
         let y = self[1];
         let z = self[2];
@@ -28,7 +28,7 @@ impl MulVectorMatrix<Mat4> for Vec4 {
         ]
     }
 
-    fn mul_matrix(&self, rhs: &Mat4) -> Self::VectorType {
+    fn mul_matrix(&self, rhs: &Mat4<T>) -> Self::VectorType {
         let x = self[0];
         let y = self[1];
         let z = self[2];
@@ -112,7 +112,7 @@ mod tests {
     }
     #[test]
     fn vec4_mag() {
-        let b = [2., 3., 4., 5.];
+        let b: Vec4 = [2., 3., 4., 5.];
         assert!(almost_eq(&[b.mag()], &[7.3484693]));
     }
     #[test]
@@ -1,3 +1,6 @@
+use crate::error::{check_len, MathError};
+use crate::scalar::Scalar;
+
 /// The base Vector trait
 ///
 /// Note that vector operations are permitted on slices.
@@ -6,6 +9,10 @@
 /// there is no secure alternative for handling this use-case.
 pub trait Vector {
     type VectorType;
+    type ScalarType: Scalar;
+
+    /// The number of elements
+    const LEN: usize;
 
     /// Create a vector filled with zeros
     fn zeros() -> Self::VectorType;
@@ -14,24 +21,51 @@ pub trait Vector {
     fn ones() -> Self::VectorType;
 
     /// Perform element-wise multiplication with the given right-hand-side operand
-    fn mul(&self, rhs: &[f32]) -> Self::VectorType;
+    fn mul(&self, rhs: &[Self::ScalarType]) -> Self::VectorType;
 
     /// Perform element-wise addition with the given right-hand-side operand
-    fn add(&self, rhs: &[f32]) -> Self::VectorType;
+    fn add(&self, rhs: &[Self::ScalarType]) -> Self::VectorType;
     /// Perform element-wise substraction with the given right-hand-side operand
-    fn sub(&self, rhs: &[f32]) -> Self::VectorType;
+    fn sub(&self, rhs: &[Self::ScalarType]) -> Self::VectorType;
 
     /// Scale the vector elment-wise by the given constant
-    fn scale(&self, factor: f32) -> Self::VectorType;
+    fn scale(&self, factor: Self::ScalarType) -> Self::VectorType;
 
     /// Calculate the magnitude of this vector
-    fn mag(&self) -> f32;
+    fn mag(&self) -> Self::ScalarType;
 
     /// Calculate the squared magnitude of this vector
-    fn mag2(&self) -> f32;
+    fn mag2(&self) -> Self::ScalarType;
 
     /// Calculate the dot product of this vector and the given right-hand-side operand
-    fn dot(&self, rhs: &[f32]) -> f32;
+    fn dot(&self, rhs: &[Self::ScalarType]) -> Self::ScalarType;
+
+    /// Linearly interpolate between this vector (`t = 0`) and the given right-hand-side operand (`t = 1`)
+    fn lerp(&self, rhs: &[Self::ScalarType], t: Self::ScalarType) -> Self::VectorType;
+
+    /// Same as `mul`, but fails if `rhs` has the wrong length
+    fn try_mul(&self, rhs: &[Self::ScalarType]) -> Result<Self::VectorType, MathError> {
+        check_len(rhs.len(), Self::LEN)?;
+        Ok(self.mul(rhs))
+    }
+
+    /// Same as `add`, but fails if `rhs` has the wrong length
+    fn try_add(&self, rhs: &[Self::ScalarType]) -> Result<Self::VectorType, MathError> {
+        check_len(rhs.len(), Self::LEN)?;
+        Ok(self.add(rhs))
+    }
+
+    /// Same as `sub`, but fails if `rhs` has the wrong length
+    fn try_sub(&self, rhs: &[Self::ScalarType]) -> Result<Self::VectorType, MathError> {
+        check_len(rhs.len(), Self::LEN)?;
+        Ok(self.sub(rhs))
+    }
+
+    /// Same as `dot`, but fails if `rhs` has the wrong length
+    fn try_dot(&self, rhs: &[Self::ScalarType]) -> Result<Self::ScalarType, MathError> {
+        check_len(rhs.len(), Self::LEN)?;
+        Ok(self.dot(rhs))
+    }
 }
 
 #[cfg(any(feature = "Matrix4", feature = "Matrix3"))]
@@ -48,53 +82,64 @@ pub trait MulVectorMatrix<Matrix> {
     fn mul_matrix(&self, rhs: &Matrix) -> Self::VectorType;
 }
 
+#[cfg(any(feature = "Vector2", feature = "Vector3", feature = "Vector4"))]
 macro_rules! impl_vector {
-    ($type:ty, $n:expr) => {
-        impl Vector for $type {
-            type VectorType = $type;
-            fn zeros() -> $type {
-                [0.; $n]
+    ($type:ident, $n:expr) => {
+        impl<T: Scalar> Vector for $type<T> {
+            type VectorType = $type<T>;
+            type ScalarType = T;
+
+            const LEN: usize = $n;
+
+            fn zeros() -> $type<T> {
+                [T::ZERO; $n]
             }
 
-            fn ones() -> $type {
-                [1.; $n]
+            fn ones() -> $type<T> {
+                [T::ONE; $n]
             }
 
-            fn mul(&self, rhs: &[f32]) -> $type {
+            fn mul(&self, rhs: &[T]) -> $type<T> {
                 let mut dst = *self;
                 mul(&mut dst, rhs);
                 dst
             }
 
-            fn add(&self, rhs: &[f32]) -> $type {
+            fn add(&self, rhs: &[T]) -> $type<T> {
                 let mut dst = *self;
                 add(&mut dst, rhs);
                 dst
             }
 
-            fn sub(&self, rhs: &[f32]) -> $type {
+            fn sub(&self, rhs: &[T]) -> $type<T> {
                 let mut dst = *self;
                 sub(&mut dst, rhs);
                 dst
             }
 
-            fn scale(&self, factor: f32) -> $type {
+            fn scale(&self, factor: T) -> $type<T> {
                 let mut dst = *self;
                 scale(&mut dst, factor);
                 dst
             }
 
-            fn mag(&self) -> f32 {
+            fn mag(&self) -> T {
                 mag(self)
             }
 
-            fn mag2(&self) -> f32 {
+            fn mag2(&self) -> T {
                 mag2(self)
             }
 
-            fn dot(&self, rhs: &[f32]) -> f32 {
+            fn dot(&self, rhs: &[T]) -> T {
                 dot(self, rhs)
             }
+
+            fn lerp(&self, rhs: &[T], t: T) -> $type<T> {
+                let mut dst = *self;
+                lerp(&mut dst, rhs, t);
+                dst
+            }
         }
     };
 }
@@ -0,0 +1,426 @@
+//! JavaScript bindings in the calling style of gl-matrix.
+//!
+//! The operations are exported as static methods of the classes `mat4`, `mat3`, `vec3` and
+//! `vec4`. All arguments are `Float32Array`s and results are written to `out`:
+//!
+//! ```js
+//! import { mat4 } from "webgl-matrix";
+//!
+//! const model = mat4.create();
+//! mat4.translate(model, model, new Float32Array([1, 2, 3]));
+//! mat4.multiply(mvp, viewProjection, model);
+//! ```
+//!
+//! Like in gl-matrix, all matrices are column-major and e.g. `mat4.multiply(out, a, b)` computes
+//! `a * b`. Arrays which are too short throw an error.
+
+use crate::mat3::Mat3;
+use crate::mat4::{Mat4, ProjectionMatrix, ViewMatrix};
+use crate::matrix::{Matrix, RotationMatrix};
+use crate::vec3::{CrossProduct, Vec3};
+use crate::vec4::Vec4;
+use crate::vector::{MulVectorMatrix, Vector};
+use wasm_bindgen::prelude::*;
+
+// Copies the first `N` values, panics (i.e. throws) if there are less
+fn load<const N: usize>(a: &[f32]) -> [f32; N] {
+    let mut out = [0.; N];
+    out.copy_from_slice(&a[..N]);
+    out
+}
+
+fn store(out: &mut [f32], values: &[f32]) {
+    out[..values.len()].copy_from_slice(values);
+}
+
+/// 4x4 matrix operations, exported as `mat4`
+#[wasm_bindgen(js_name = mat4)]
+pub struct JsMat4;
+
+#[wasm_bindgen(js_class = mat4)]
+impl JsMat4 {
+    /// Create a new identity matrix
+    pub fn create() -> Vec<f32> {
+        Mat4::<f32>::identity().to_vec()
+    }
+
+    /// Set `out` to the identity matrix
+    pub fn identity(out: &mut [f32]) {
+        store(out, &Mat4::<f32>::identity());
+    }
+
+    /// Copy `a` to `out`
+    pub fn copy(out: &mut [f32], a: &[f32]) {
+        store(out, &load::<16>(a));
+    }
+
+    /// Compute `out = a * b`
+    pub fn multiply(out: &mut [f32], a: &[f32], b: &[f32]) {
+        // `x.mul(y)` computes `y * x` in column-major notation
+        let mut m = load::<16>(b);
+        m.mul(&load(a));
+        store(out, &m);
+    }
+
+    /// Invert `a`. Returns `false` and leaves `out` untouched if `a` is singular.
+    pub fn invert(out: &mut [f32], a: &[f32]) -> bool {
+        let mut m = load::<16>(a);
+        if m.inverse().is_none() {
+            return false;
+        }
+        store(out, &m);
+        true
+    }
+
+    /// Transpose `a`
+    pub fn transpose(out: &mut [f32], a: &[f32]) {
+        let mut m = load::<16>(a);
+        m.transpose();
+        store(out, &m);
+    }
+
+    /// Compute the determinant of `a`
+    pub fn determinant(a: &[f32]) -> f32 {
+        load::<16>(a).det()
+    }
+
+    /// Translate `a` by the vector `v`
+    pub fn translate(out: &mut [f32], a: &[f32], v: &[f32]) {
+        let mut m = load::<16>(a);
+        m.translate(&v[..3]);
+        store(out, &m);
+    }
+
+    /// Scale the first three columns of `a` by the vector `v`
+    pub fn scale(out: &mut [f32], a: &[f32], v: &[f32]) {
+        let mut m = load::<16>(a);
+        for (col, &s) in v[..3].iter().enumerate() {
+            for x in &mut m[col * 4..col * 4 + 4] {
+                *x *= s;
+            }
+        }
+        store(out, &m);
+    }
+
+    /// Rotate `a` by `rad` around `axis`. Returns `false` and leaves `out` untouched if the
+    /// axis has (almost) zero length.
+    pub fn rotate(out: &mut [f32], a: &[f32], rad: f32, axis: &[f32]) -> bool {
+        if load::<3>(axis).mag() <= crate::utils::EPSILON {
+            return false;
+        }
+        let mut m = load::<16>(a);
+        m.rotate(rad, &axis[..3]);
+        store(out, &m);
+        true
+    }
+
+    /// Rotate `a` by `rad` around the X-axis
+    #[wasm_bindgen(js_name = rotateX)]
+    pub fn rotate_x(out: &mut [f32], a: &[f32], rad: f32) {
+        store(out, load::<16>(a).rotate_x(rad));
+    }
+
+    /// Rotate `a` by `rad` around the Y-axis
+    #[wasm_bindgen(js_name = rotateY)]
+    pub fn rotate_y(out: &mut [f32], a: &[f32], rad: f32) {
+        store(out, load::<16>(a).rotate_y(rad));
+    }
+
+    /// Rotate `a` by `rad` around the Z-axis
+    #[wasm_bindgen(js_name = rotateZ)]
+    pub fn rotate_z(out: &mut [f32], a: &[f32], rad: f32) {
+        store(out, load::<16>(a).rotate_z(rad));
+    }
+
+    /// Create a perspective projection
+    pub fn perspective(out: &mut [f32], fovy: f32, aspect: f32, near: f32, far: f32) {
+        store(out, &Mat4::create_perspective(fovy, aspect, near, far));
+    }
+
+    /// Create an orthogonal projection
+    pub fn ortho(
+        out: &mut [f32],
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        near: f32,
+        far: f32,
+    ) {
+        store(
+            out,
+            &Mat4::create_orthogonal_from_viewport(left, right, bottom, top, near, far),
+        );
+    }
+
+    /// Create a right-handed view matrix
+    #[wasm_bindgen(js_name = lookAt)]
+    pub fn look_at(out: &mut [f32], eye: &[f32], center: &[f32], up: &[f32]) {
+        store(out, &Mat4::look_at_rh(&load(eye), &load(center), &load(up)));
+    }
+}
+
+/// 3x3 matrix operations, exported as `mat3`
+#[wasm_bindgen(js_name = mat3)]
+pub struct JsMat3;
+
+#[wasm_bindgen(js_class = mat3)]
+impl JsMat3 {
+    /// Create a new identity matrix
+    pub fn create() -> Vec<f32> {
+        Mat3::<f32>::identity().to_vec()
+    }
+
+    /// Set `out` to the identity matrix
+    pub fn identity(out: &mut [f32]) {
+        store(out, &Mat3::<f32>::identity());
+    }
+
+    /// Copy `a` to `out`
+    pub fn copy(out: &mut [f32], a: &[f32]) {
+        store(out, &load::<9>(a));
+    }
+
+    /// Compute `out = a * b`
+    pub fn multiply(out: &mut [f32], a: &[f32], b: &[f32]) {
+        // `x.mul(y)` computes `y * x` in column-major notation
+        let mut m = load::<9>(b);
+        m.mul(&load(a));
+        store(out, &m);
+    }
+
+    /// Invert `a`. Returns `false` and leaves `out` untouched if `a` is singular.
+    pub fn invert(out: &mut [f32], a: &[f32]) -> bool {
+        let mut m = load::<9>(a);
+        if m.inverse().is_none() {
+            return false;
+        }
+        store(out, &m);
+        true
+    }
+
+    /// Transpose `a`
+    pub fn transpose(out: &mut [f32], a: &[f32]) {
+        let mut m = load::<9>(a);
+        m.transpose();
+        store(out, &m);
+    }
+
+    /// Compute the determinant of `a`
+    pub fn determinant(a: &[f32]) -> f32 {
+        load::<9>(a).det()
+    }
+
+    /// Copy the upper-left 3x3 part of the 4x4 matrix `a`
+    #[wasm_bindgen(js_name = fromMat4)]
+    pub fn from_mat4(out: &mut [f32], a: &[f32]) {
+        let a = load::<16>(a);
+        store(
+            out,
+            &[a[0], a[1], a[2], a[4], a[5], a[6], a[8], a[9], a[10]],
+        );
+    }
+
+    /// Compute the normal matrix (inverse transpose) of the 4x4 matrix `a`. Returns `false`
+    /// and leaves `out` untouched if `a` is singular.
+    #[wasm_bindgen(js_name = normalFromMat4)]
+    pub fn normal_from_mat4(out: &mut [f32], a: &[f32]) -> bool {
+        let mut m = [0.; 9];
+        JsMat3::from_mat4(&mut m, a);
+        if m.inverse().is_none() {
+            return false;
+        }
+        m.transpose();
+        store(out, &m);
+        true
+    }
+}
+
+/// 3-dimensional vector operations, exported as `vec3`
+#[wasm_bindgen(js_name = vec3)]
+pub struct JsVec3;
+
+#[wasm_bindgen(js_class = vec3)]
+impl JsVec3 {
+    /// Create a new zero vector
+    pub fn create() -> Vec<f32> {
+        Vec3::<f32>::zeros().to_vec()
+    }
+
+    /// Compute `out = a + b`
+    pub fn add(out: &mut [f32], a: &[f32], b: &[f32]) {
+        store(out, &load::<3>(a).add(&b[..3]));
+    }
+
+    /// Compute `out = a - b`
+    pub fn subtract(out: &mut [f32], a: &[f32], b: &[f32]) {
+        store(out, &load::<3>(a).sub(&b[..3]));
+    }
+
+    /// Compute `out = a * s`
+    pub fn scale(out: &mut [f32], a: &[f32], s: f32) {
+        store(out, &load::<3>(a).scale(s));
+    }
+
+    /// Compute the dot product of `a` and `b`
+    pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+        load::<3>(a).dot(&b[..3])
+    }
+
+    /// Compute the cross product of `a` and `b`
+    pub fn cross(out: &mut [f32], a: &[f32], b: &[f32]) {
+        store(out, &load::<3>(a).cross(&load(b)));
+    }
+
+    /// Compute the length of `a`
+    pub fn length(a: &[f32]) -> f32 {
+        load::<3>(a).mag()
+    }
+
+    /// Scale `a` to unit length. Zero vectors stay zero.
+    pub fn normalize(out: &mut [f32], a: &[f32]) {
+        let v = load::<3>(a);
+        let mag = v.mag();
+        store(out, &if mag > 0. { v.scale(1. / mag) } else { v });
+    }
+
+    /// Transform the point `a` by the 4x4 matrix `m`, including the perspective division
+    #[wasm_bindgen(js_name = transformMat4)]
+    pub fn transform_mat4(out: &mut [f32], a: &[f32], m: &[f32]) {
+        let v = load::<3>(a).mul_matrix(&load::<16>(m));
+        let w = if v[3] != 0. { v[3] } else { 1. };
+        store(out, &[v[0] / w, v[1] / w, v[2] / w]);
+    }
+
+    /// Transform `a` by the 3x3 matrix `m`
+    #[wasm_bindgen(js_name = transformMat3)]
+    pub fn transform_mat3(out: &mut [f32], a: &[f32], m: &[f32]) {
+        store(out, &load::<3>(a).mul_matrix(&load::<9>(m)));
+    }
+}
+
+/// 4-dimensional vector operations, exported as `vec4`
+#[wasm_bindgen(js_name = vec4)]
+pub struct JsVec4;
+
+#[wasm_bindgen(js_class = vec4)]
+impl JsVec4 {
+    /// Create a new zero vector
+    pub fn create() -> Vec<f32> {
+        Vec4::<f32>::zeros().to_vec()
+    }
+
+    /// Compute `out = a + b`
+    pub fn add(out: &mut [f32], a: &[f32], b: &[f32]) {
+        store(out, &load::<4>(a).add(&b[..4]));
+    }
+
+    /// Compute `out = a - b`
+    pub fn subtract(out: &mut [f32], a: &[f32], b: &[f32]) {
+        store(out, &load::<4>(a).sub(&b[..4]));
+    }
+
+    /// Compute `out = a * s`
+    pub fn scale(out: &mut [f32], a: &[f32], s: f32) {
+        store(out, &load::<4>(a).scale(s));
+    }
+
+    /// Compute the dot product of `a` and `b`
+    pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+        load::<4>(a).dot(&b[..4])
+    }
+
+    /// Compute the length of `a`
+    pub fn length(a: &[f32]) -> f32 {
+        load::<4>(a).mag()
+    }
+
+    /// Scale `a` to unit length. Zero vectors stay zero.
+    pub fn normalize(out: &mut [f32], a: &[f32]) {
+        let v = load::<4>(a);
+        let mag = v.mag();
+        store(out, &if mag > 0. { v.scale(1. / mag) } else { v });
+    }
+
+    /// Transform `a` by the 4x4 matrix `m`
+    #[wasm_bindgen(js_name = transformMat4)]
+    pub fn transform_mat4(out: &mut [f32], a: &[f32], m: &[f32]) {
+        store(out, &load::<16>(m).mul_vector_left(&a[..4]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::almost_eq;
+
+    #[test]
+    fn wasm_mat4_multiply_order() {
+        let id: Mat4 = Mat4::identity();
+        let mut a = JsMat4::create();
+        JsMat4::translate(&mut a, &id, &[1., 2., 3.]);
+        let mut b = JsMat4::create();
+        JsMat4::scale(&mut b, &id, &[2., 2., 2.]);
+
+        // `a * b` applies the scale first
+        let mut ab = JsMat4::create();
+        JsMat4::multiply(&mut ab, &a, &b);
+        let mut p = [0.; 3];
+        JsVec3::transform_mat4(&mut p, &[1., 1., 1.], &ab);
+        assert!(almost_eq(&p, &[3., 4., 5.]));
+    }
+
+    #[test]
+    fn wasm_mat4_invert() {
+        let id: Mat4 = Mat4::identity();
+        let mut r = JsMat4::create();
+        assert!(JsMat4::rotate(&mut r, &id, 0.5, &[1., 1., 0.]));
+        let mut m = JsMat4::create();
+        JsMat4::translate(&mut m, &r, &[1., 2., 3.]);
+
+        let mut inv = [0.; 16];
+        assert!(JsMat4::invert(&mut inv, &m));
+        let mut r = [0.; 16];
+        JsMat4::multiply(&mut r, &m, &inv);
+        assert!(almost_eq(&r, &id));
+
+        let mut singular = [7.; 16];
+        assert!(!JsMat4::invert(&mut singular, &[0.; 16]));
+        assert_eq!(singular, [7.; 16]);
+        assert!(!JsMat4::rotate(&mut singular, &m, 0.5, &[0., 0., 0.]));
+    }
+
+    #[test]
+    fn wasm_mat3_normal_from_mat4() {
+        let id: Mat4 = Mat4::identity();
+        let mut s = JsMat4::create();
+        JsMat4::scale(&mut s, &id, &[2., 4., 8.]);
+        let mut m = JsMat4::create();
+        JsMat4::translate(&mut m, &s, &[1., 2., 3.]);
+
+        let mut n = [0.; 9];
+        assert!(JsMat3::normal_from_mat4(&mut n, &m));
+        assert!(almost_eq(&n, &[0.5, 0., 0., 0., 0.25, 0., 0., 0., 0.125]));
+    }
+
+    #[test]
+    fn wasm_vectors() {
+        let mut v = [0.; 3];
+        JsVec3::cross(&mut v, &[1., 0., 0.], &[0., 1., 0.]);
+        assert_eq!(v, [0., 0., 1.]);
+        JsVec3::normalize(&mut v, &[0., 3., 4.]);
+        assert!(almost_eq(&v, &[0., 0.6, 0.8]));
+
+        let mut m = JsMat4::create();
+        JsMat4::translate(&mut m, &Mat4::<f32>::identity(), &[1., 2., 3.]);
+        let mut w = [0.; 4];
+        JsVec4::transform_mat4(&mut w, &[1., 1., 1., 1.], &m);
+        assert_eq!(w, [2., 3., 4., 1.]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wasm_short_array() {
+        JsMat4::identity(&mut [0.; 9]);
+    }
+}
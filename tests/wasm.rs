@@ -0,0 +1,64 @@
+//! Runs the JavaScript bindings under Node:
+//!
+//! ```sh
+//! cargo test --target wasm32-unknown-unknown --features wasm-bindgen --test wasm
+//! ```
+#![cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+
+use wasm_bindgen_test::*;
+use webgl_matrix::wasm::{JsMat3, JsMat4, JsVec3, JsVec4};
+
+fn almost_eq(a: &[f32], b: &[f32]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5)
+}
+
+#[wasm_bindgen_test]
+fn wasm_mat4_multiply() {
+    let id = JsMat4::create();
+    let mut t = JsMat4::create();
+    JsMat4::translate(&mut t, &id, &[1., 2., 3.]);
+    let mut s = JsMat4::create();
+    JsMat4::scale(&mut s, &id, &[2., 2., 2.]);
+
+    let mut ts = JsMat4::create();
+    JsMat4::multiply(&mut ts, &t, &s);
+
+    let mut p = JsVec3::create();
+    JsVec3::transform_mat4(&mut p, &[1., 1., 1.], &ts);
+    assert!(almost_eq(&p, &[3., 4., 5.]));
+}
+
+#[wasm_bindgen_test]
+fn wasm_mat4_invert() {
+    let id = JsMat4::create();
+    let mut m = JsMat4::create();
+    assert!(JsMat4::rotate(&mut m, &id, 1., &[0., 1., 0.]));
+
+    let mut inv = JsMat4::create();
+    assert!(JsMat4::invert(&mut inv, &m));
+    let mut r = JsMat4::create();
+    JsMat4::multiply(&mut r, &inv, &m);
+    assert!(almost_eq(&r, &id));
+}
+
+#[wasm_bindgen_test]
+fn wasm_mat3_normal_from_mat4() {
+    let id = JsMat4::create();
+    let mut m = JsMat4::create();
+    JsMat4::scale(&mut m, &id, &[2., 2., 2.]);
+
+    let mut n = JsMat3::create();
+    assert!(JsMat3::normal_from_mat4(&mut n, &m));
+    assert!(almost_eq(&n, &[0.5, 0., 0., 0., 0.5, 0., 0., 0., 0.5]));
+}
+
+#[wasm_bindgen_test]
+fn wasm_vec4_transform_mat4() {
+    let id = JsMat4::create();
+    let mut m = JsMat4::create();
+    JsMat4::translate(&mut m, &id, &[1., 2., 3.]);
+
+    let mut v = JsVec4::create();
+    JsVec4::transform_mat4(&mut v, &[1., 1., 1., 1.], &m);
+    assert!(almost_eq(&v, &[2., 3., 4., 1.]));
+}